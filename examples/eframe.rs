@@ -1,7 +1,5 @@
 use eframe::{NativeOptions, Result};
-use egui_spine::{
-    Animation, AnimationId, Scene, SkeletonKind, Spine, SpineOptions, init_wgpu_spine_context,
-};
+use egui_spine::{SkeletonKind, Spine, SpineOptions, init_wgpu_spine_context};
 use glam::vec2;
 
 fn main() -> Result<()> {
@@ -26,25 +24,19 @@ impl App {
         let render_state = cc.wgpu_render_state.as_ref().unwrap();
         init_wgpu_spine_context(render_state, Default::default());
 
-        let options = SpineOptions {
-            scene: Scene {
-                position: vec2(100., -360.),
-                scale: 0.70,
-                ..Default::default()
-            },
-            animation: Animation {
-                id: AnimationId::Index(2),
-                ..Default::default()
-            },
-        };
-        Self {
-            spine: Spine::new(
-                "assets/spineboy/export/spineboy.atlas",
-                SkeletonKind::Json("assets/spineboy/export/spineboy-ess.json"),
-                options,
-            )
-            .unwrap(),
-        }
+        let options = SpineOptions::builder()
+            .position(vec2(100., -360.))
+            .scale(0.70)
+            .animation_index(2)
+            .build();
+        let spine = Spine::new(
+            "assets/spineboy/export/spineboy.atlas",
+            SkeletonKind::Json("assets/spineboy/export/spineboy-ess.json"),
+            options,
+        )
+        .unwrap();
+        spine.preload(render_state);
+        Self { spine }
     }
 }
 
@@ -0,0 +1,51 @@
+use eframe::{NativeOptions, Result};
+use egui_spine::{SkeletonKind, Spine, SpineOptions, init_glow_spine_context};
+use glam::vec2;
+
+fn main() -> Result<()> {
+    let native_options = NativeOptions {
+        renderer: eframe::Renderer::Glow,
+        ..Default::default()
+    };
+
+    eframe::run_native(
+        "Spine egui (glow)",
+        native_options,
+        Box::new(|cc| Ok(Box::new(App::new(cc)))),
+    )
+}
+
+pub struct App {
+    spine: Spine,
+}
+
+impl App {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let gl = cc.gl.as_ref().unwrap();
+        init_glow_spine_context(gl, Default::default()).unwrap();
+
+        let options = SpineOptions::builder()
+            .position(vec2(100., -360.))
+            .scale(0.70)
+            .animation_index(2)
+            .build();
+        Self {
+            spine: Spine::new(
+                "assets/spineboy/export/spineboy.atlas",
+                SkeletonKind::Json("assets/spineboy/export/spineboy-ess.json"),
+                options,
+            )
+            .unwrap(),
+        }
+    }
+}
+
+impl eframe::App for App {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default()
+            .frame(Default::default())
+            .show(ctx, |ui| {
+                let _ = ui.add(&mut self.spine);
+            });
+    }
+}
@@ -0,0 +1,148 @@
+use rusty_spine::SpineError as RuntimeError;
+use std::{
+    error::Error,
+    fmt,
+    path::{Path, PathBuf},
+};
+
+/// An error while reading or changing a [`Spine`] model.
+///
+/// [`Spine`]: crate::Spine
+#[derive(Debug)]
+pub enum SpineError {
+    /// The model is still being rendered, i.e. it was added as a widget and
+    /// the frame wasn't painted yet; see [`Spine::clone_instance`] to draw a
+    /// copy instead.
+    ///
+    /// [`Spine::clone_instance`]: crate::Spine::clone_instance
+    Busy,
+    /// Something couldn't be found, represented by `what` it was (e.g. a slot
+    /// or an animation) and its `name`.
+    NotFound { what: String, name: String },
+    /// Any other error from the Spine runtime.
+    Runtime(RuntimeError),
+}
+
+impl From<RuntimeError> for SpineError {
+    fn from(error: RuntimeError) -> Self {
+        match error {
+            RuntimeError::NotFound { what, name } => Self::NotFound { what, name },
+            error => Self::Runtime(error),
+        }
+    }
+}
+
+impl fmt::Display for SpineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpineError::Busy => write!(f, "The Spine model is still being rendered"),
+            SpineError::NotFound { what, name } => write!(f, "{what} not found: {name}"),
+            SpineError::Runtime(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for SpineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SpineError::Runtime(error) => Some(error),
+            SpineError::Busy | SpineError::NotFound { .. } => None,
+        }
+    }
+}
+
+/// An error while loading a [`Spine`] model, telling which of its files (and
+/// which loading step) caused it.
+///
+/// [`Spine`]: crate::Spine
+#[derive(Debug)]
+pub enum LoadError {
+    /// The atlas couldn't be read.
+    Atlas { path: PathBuf, source: RuntimeError },
+    /// The JSON skeleton couldn't be read or parsed.
+    Json { path: PathBuf, source: RuntimeError },
+    /// The binary skeleton couldn't be read or parsed.
+    Binary { path: PathBuf, source: RuntimeError },
+    /// The skeleton was exported by a version of the Spine editor that isn't
+    /// supported by the runtime; see [`LoadError::SUPPORTED_VERSION`].
+    UnsupportedVersion { path: PathBuf, version: String },
+    /// The format of a [`SkeletonKind::Auto`] skeleton couldn't be detected,
+    /// either because it isn't a skeleton or because it couldn't be read.
+    ///
+    /// [`SkeletonKind::Auto`]: crate::SkeletonKind::Auto
+    UnknownFormat { path: PathBuf },
+    /// The skin or animation set in the options doesn't exist in the skeleton.
+    Options(SpineError),
+}
+
+impl LoadError {
+    /// The `major.minor` version of the Spine editor whose exports can be
+    /// loaded.
+    pub const SUPPORTED_VERSION: &str = "4.2";
+
+    pub(crate) fn is_supported_version(version: &str) -> bool {
+        version
+            .strip_prefix(Self::SUPPORTED_VERSION)
+            .is_some_and(|patch| patch.is_empty() || patch.starts_with('.'))
+    }
+
+    /// The file that failed to load, if the error comes from one.
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            LoadError::Atlas { path, .. }
+            | LoadError::Json { path, .. }
+            | LoadError::Binary { path, .. }
+            | LoadError::UnsupportedVersion { path, .. }
+            | LoadError::UnknownFormat { path } => Some(path),
+            LoadError::Options(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Atlas { path, source } => {
+                write!(f, "Failed to load atlas {}: {source}", path.display())
+            }
+            LoadError::Json { path, source } => {
+                write!(
+                    f,
+                    "Failed to load JSON skeleton {}: {source}",
+                    path.display()
+                )
+            }
+            LoadError::Binary { path, source } => {
+                write!(
+                    f,
+                    "Failed to load binary skeleton {}: {source}",
+                    path.display()
+                )
+            }
+            LoadError::UnsupportedVersion { path, version } => write!(
+                f,
+                "Skeleton {} was exported with Spine {version}, but only Spine {}.x is supported",
+                path.display(),
+                Self::SUPPORTED_VERSION
+            ),
+            LoadError::UnknownFormat { path } => write!(
+                f,
+                "Couldn't tell whether skeleton {} is JSON or binary",
+                path.display()
+            ),
+            LoadError::Options(source) => write!(f, "Invalid options: {source}"),
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Atlas { source, .. }
+            | LoadError::Json { source, .. }
+            | LoadError::Binary { source, .. } => Some(source),
+            LoadError::Options(source) => Some(source),
+            LoadError::UnsupportedVersion { .. } | LoadError::UnknownFormat { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use rusty_spine::AnimationEvent;
+use std::{
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+/// An event emitted by the animation state while updating a [`Spine`].
+///
+/// [`Spine`]: crate::Spine
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpineEvent {
+    /// The track the event originated from.
+    pub track: usize,
+    pub kind: SpineEventKind,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SpineEventKind {
+    Start,
+    Interrupt,
+    End,
+    Complete,
+    Dispose,
+    /// A user defined event keyed in the animation.
+    Event {
+        name: String,
+        /// The animation time this event was keyed.
+        time: f32,
+        int: i32,
+        float: f32,
+        /// The event's string value or an empty string.
+        string: String,
+    },
+}
+
+impl SpineEventKind {
+    fn kinds(&self) -> EventKinds {
+        match self {
+            SpineEventKind::Start => EventKinds::Start,
+            SpineEventKind::Interrupt => EventKinds::Interrupt,
+            SpineEventKind::End => EventKinds::End,
+            SpineEventKind::Complete => EventKinds::Complete,
+            SpineEventKind::Dispose => EventKinds::Dispose,
+            SpineEventKind::Event { .. } => EventKinds::Event,
+        }
+    }
+}
+
+impl From<AnimationEvent<'_>> for SpineEvent {
+    fn from(event: AnimationEvent<'_>) -> Self {
+        let (track_entry, kind) = match event {
+            AnimationEvent::Start { track_entry } => (track_entry, SpineEventKind::Start),
+            AnimationEvent::Interrupt { track_entry } => (track_entry, SpineEventKind::Interrupt),
+            AnimationEvent::End { track_entry } => (track_entry, SpineEventKind::End),
+            AnimationEvent::Complete { track_entry } => (track_entry, SpineEventKind::Complete),
+            AnimationEvent::Dispose { track_entry } => (track_entry, SpineEventKind::Dispose),
+            AnimationEvent::Event {
+                track_entry,
+                name,
+                time,
+                int,
+                float,
+                string,
+                ..
+            } => (
+                track_entry,
+                SpineEventKind::Event {
+                    name: name.to_owned(),
+                    time,
+                    int,
+                    float,
+                    string: string.to_owned(),
+                },
+            ),
+        };
+
+        Self {
+            track: track_entry.track_index(),
+            kind,
+        }
+    }
+}
+
+/// Callback invoked for every [`SpineEvent`] that passes its [`EventFilter`].
+///
+/// NOTE: The closure is reference counted, so that `SpineOptions` can still be
+/// cloned; clones share the same closure.
+#[derive(Clone)]
+pub struct EventCallback {
+    filter: EventFilter,
+    callback: Arc<Mutex<dyn FnMut(SpineEvent) + Send>>,
+}
+
+impl EventCallback {
+    /// Calls `callback` with every event.
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: FnMut(SpineEvent) + Send + 'static,
+    {
+        Self {
+            filter: EventFilter::default(),
+            callback: Arc::new(Mutex::new(callback)),
+        }
+    }
+
+    /// Only calls the callback with the events that pass `filter`.
+    pub fn with_filter(mut self, filter: EventFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    pub fn filter(&self) -> EventFilter {
+        self.filter
+    }
+
+    pub(crate) fn call(&self, event: SpineEvent) {
+        if !self.filter.contains(event.track, event.kind.kinds()) {
+            return;
+        }
+        let mut callback = self
+            .callback
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        callback(event);
+    }
+}
+
+impl fmt::Debug for EventCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventCallback")
+            .field("filter", &self.filter)
+            .finish_non_exhaustive()
+    }
+}
+
+bitflags::bitflags! {
+    /// Kinds of [`SpineEvent`]s, to filter them; see [`EventFilter`].
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct EventKinds: u8 {
+        const Start = 0b000001;
+        const Interrupt = 0b000010;
+        const End = 0b000100;
+        const Complete = 0b001000;
+        const Dispose = 0b010000;
+        /// User defined events keyed in the animations.
+        const Event = 0b100000;
+    }
+}
+
+/// Which [`SpineEvent`]s are passed to an [`EventCallback`]; see
+/// [`Spine::on_event`].
+///
+/// [`Spine::on_event`]: crate::Spine::on_event
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EventFilter {
+    /// Only dispatch the events of this track; `None` dispatches the events of
+    /// every track.
+    pub track: Option<usize>,
+    pub kinds: EventKinds,
+}
+
+impl EventFilter {
+    /// Filters out every event.
+    pub(crate) const NONE: Self = Self {
+        track: None,
+        kinds: EventKinds::empty(),
+    };
+
+    fn contains(&self, track: usize, kinds: EventKinds) -> bool {
+        self.kinds.contains(kinds) && self.track.is_none_or(|filter| filter == track)
+    }
+
+    fn matches(&self, event: &AnimationEvent<'_>) -> bool {
+        let (track_entry, kind) = match event {
+            AnimationEvent::Start { track_entry } => (track_entry, EventKinds::Start),
+            AnimationEvent::Interrupt { track_entry } => (track_entry, EventKinds::Interrupt),
+            AnimationEvent::End { track_entry } => (track_entry, EventKinds::End),
+            AnimationEvent::Complete { track_entry } => (track_entry, EventKinds::Complete),
+            AnimationEvent::Dispose { track_entry } => (track_entry, EventKinds::Dispose),
+            AnimationEvent::Event { track_entry, .. } => (track_entry, EventKinds::Event),
+        };
+
+        self.contains(track_entry.track_index(), kind)
+    }
+}
+
+impl Default for EventFilter {
+    fn default() -> Self {
+        Self {
+            track: None,
+            kinds: EventKinds::all(),
+        }
+    }
+}
+
+/// Collects the events emitted by the animation state listener, until they
+/// are dispatched.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EventQueue(Arc<Mutex<QueuedEvents>>);
+
+#[derive(Debug, Default)]
+struct QueuedEvents {
+    filter: EventFilter,
+    events: Vec<SpineEvent>,
+}
+
+impl EventQueue {
+    /// Queues `event`, unless it is filtered out.
+    ///
+    /// NOTE: Filtered events are skipped before being converted, to not
+    /// allocate the names of events that nobody listens to.
+    pub(crate) fn push(&self, event: AnimationEvent<'_>) {
+        let mut queued = self.lock();
+        if queued.filter.matches(&event) {
+            queued.events.push(event.into());
+        }
+    }
+
+    pub(crate) fn take(&self) -> Vec<SpineEvent> {
+        std::mem::take(&mut self.lock().events)
+    }
+
+    pub(crate) fn set_filter(&self, filter: EventFilter) {
+        self.lock().filter = filter;
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, QueuedEvents> {
+        self.0.lock().unwrap_or_else(|error| error.into_inner())
+    }
+}
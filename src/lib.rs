@@ -1,187 +1,2365 @@
+use bytemuck::{Pod, Zeroable};
+#[cfg(any(feature = "wgpu", feature = "glow"))]
 use egui::{Response, Ui, Widget};
-use glam::{Mat4, Vec2, vec3};
+use event::EventQueue;
+use glam::{Affine2, Mat4, Vec2, Vec4, vec2, vec3};
+#[cfg(any(feature = "wgpu", feature = "glow"))]
 use renderer::{Meshes, RendererCallback};
 use rusty_spine::{
-    AnimationStateData, Atlas, Physics, SkeletonBinary, SkeletonData, SkeletonJson, SpineError,
-    controller::{SkeletonController, SkeletonControllerSettings},
-    draw::{ColorSpace, CullDirection},
+    AnimationState, AnimationStateData, Bone, Skeleton, SkeletonBinary, SkeletonClipping,
+    SkeletonData, SkeletonJson, Slot, TrackEntry,
+    c_interface::CTmpMut,
+    controller::{SkeletonCombinedRenderable, SkeletonController, SkeletonControllerSettings},
+};
+use std::{
+    borrow::Cow,
+    ffi::CStr,
+    path::{Path, PathBuf},
+    sync::Arc,
 };
-use std::{borrow::Cow, path::Path, sync::Arc};
 
+mod error;
+mod event;
+#[cfg(any(feature = "wgpu", feature = "glow"))]
 mod renderer;
 
-pub use renderer::Face;
+pub use error::{LoadError, SpineError};
+pub use event::{EventCallback, EventFilter, EventKinds, SpineEvent, SpineEventKind};
+#[cfg(feature = "glow")]
+pub use renderer::glow::{GlowContextOptions, init_glow_spine_context};
+#[cfg(feature = "wgpu")]
 pub use renderer::wgpu::{WgpuContextOptions, init_wgpu_spine_context};
-
-// TODO(Unavailable): Feature gate non strictly necessary dependencies.
+#[cfg(any(feature = "wgpu", feature = "glow"))]
+pub use renderer::{
+    BlendComponent, BlendFactor, BlendState, SpineBlendMode, TextureDecoder, TextureError,
+    TextureLoader,
+};
+pub use rusty_spine::{
+    Atlas, BlendMode,
+    atlas::{AtlasFilter, AtlasWrap},
+    draw::{ColorSpace, CullDirection},
+};
 
 #[derive(Debug)]
 pub struct Spine {
     options: SpineOptions,
+    atlas: Arc<Atlas>,
+    skeleton_data: Arc<SkeletonData>,
     controller: Arc<SkeletonController>,
+    events: EventQueue,
+    /// Events kept for [`Spine::drain_events`].
+    queued_events: Vec<SpineEvent>,
+    paused: bool,
+    draw_stats: DrawStats,
+    source: Source,
+}
+
+impl Spine {
+    /// Loads the atlas and skeleton files of a model.
+    ///
+    /// Models can be loaded and updated without initializing any renderer
+    /// context, e.g. in headless tests; but models that are drawn need to be
+    /// loaded after it is initialized, since the textures of the atlas pages
+    /// are set up by the context when the atlas is loaded.
+    ///
+    /// # Errors
+    ///
+    /// If any of the files can't be read or parsed, or if the initial skin or
+    /// animation doesn't exist.
+    pub fn new<A, S>(
+        atlas: A,
+        skel: SkeletonKind<S>,
+        options: SpineOptions,
+    ) -> Result<Self, LoadError>
+    where
+        A: AsRef<Path>,
+        S: AsRef<Path>,
+    {
+        let atlas_path = atlas.as_ref();
+        Self::__with_atlas(
+            Self::load_atlas(atlas_path)?,
+            Some(atlas_path),
+            skel.as_ref(),
+            options,
+        )
+    }
+
+    /// Loads the atlas at `path`, to share it, and the textures of its pages,
+    /// between several models with [`Spine::with_atlas`].
+    ///
+    /// NOTE: The textures are registered when the atlas is loaded, so the
+    /// renderer context needs to be initialized beforehand.
+    ///
+    /// # Errors
+    ///
+    /// If the atlas can't be read.
+    pub fn load_atlas<P: AsRef<Path>>(path: P) -> Result<Arc<Atlas>, LoadError> {
+        // NOTE: The paths of the pages are joined to the directory of the
+        // atlas, so they are made absolute to keep them valid even if the
+        // working directory changes before their textures are loaded.
+        let path = path.as_ref();
+        let absolute_path = std::path::absolute(path).unwrap_or_else(|_| path.to_owned());
+        let atlas = Atlas::new_from_file(absolute_path).map_err(|source| LoadError::Atlas {
+            path: path.to_owned(),
+            source,
+        })?;
+        Ok(Arc::new(atlas))
+    }
+
+    /// Like [`Spine::new`], but reuses an `atlas` loaded with
+    /// [`Spine::load_atlas`] instead of loading it again.
+    ///
+    /// # Errors
+    ///
+    /// If the skeleton can't be read or parsed, or if the initial skin or
+    /// animation doesn't exist.
+    pub fn with_atlas<S: AsRef<Path>>(
+        atlas: Arc<Atlas>,
+        skel: SkeletonKind<S>,
+        options: SpineOptions,
+    ) -> Result<Self, LoadError> {
+        Self::__with_atlas(atlas, None, skel.as_ref(), options)
+    }
+
+    fn __with_atlas(
+        atlas: Arc<Atlas>,
+        atlas_path: Option<&Path>,
+        skel: SkeletonKind<&Path>,
+        options: SpineOptions,
+    ) -> Result<Self, LoadError> {
+        let source = Source {
+            atlas: atlas_path.map(Path::to_owned),
+            skeleton: skel.to_path_buf(),
+        };
+        let premultiplied_alpha = options
+            .premultiplied_alpha
+            .unwrap_or_else(|| atlas.pages().any(|page| page.pma()));
+        let skel = Arc::new(skel.read(atlas.clone())?);
+
+        Self::from_skeleton_data(atlas, skel, premultiplied_alpha, options, source)
+            .map_err(LoadError::Options)
+    }
+
+    fn from_skeleton_data(
+        atlas: Arc<Atlas>,
+        skel: Arc<SkeletonData>,
+        premultiplied_alpha: bool,
+        options: SpineOptions,
+        source: Source,
+    ) -> Result<Self, SpineError> {
+        let mut animation_state_data = AnimationStateData::new(skel.clone());
+        animation_state_data.set_default_mix(options.default_mix);
+        let animation_state_data = Arc::new(animation_state_data);
+        let controller = SkeletonController::new(skel.clone(), animation_state_data);
+        let settings = SkeletonControllerSettings {
+            color_space: options.color_space,
+            cull_direction: options.cull_direction,
+            premultiplied_alpha,
+        };
+        let mut controller = controller.with_settings(settings);
+
+        let events = EventQueue::default();
+        events.set_filter(options.listened_events());
+        controller.animation_state.set_listener({
+            let events = events.clone();
+            move |_, event| events.push(event)
+        });
+
+        let mut spine = Self {
+            options,
+            atlas,
+            skeleton_data: skel,
+            controller: Arc::new(controller),
+            events,
+            queued_events: Vec::new(),
+            paused: false,
+            draw_stats: DrawStats::default(),
+            source,
+        };
+
+        if let Some(skin) = spine.options.animation.skin.clone() {
+            spine.set_skin(&skin)?;
+        }
+
+        let loop_ = spine.options.animation.loop_;
+        match spine.options.animation.id.clone() {
+            Some(AnimationId::Index(index)) => spine.set_animation_by_index(0, index, loop_)?,
+            Some(AnimationId::Name(name)) => spine.set_animation_by_name(0, &name, loop_)?,
+            None => {}
+        };
+
+        Ok(spine)
+    }
+
+    /// Creates another instance of the same model, with its own skeleton and
+    /// animation state, but sharing the loaded skeleton data and textures.
+    ///
+    /// The new instance starts as described by its options, instead of from
+    /// the current pose; mixes set with [`Spine::set_mix`] aren't carried
+    /// over.
+    ///
+    /// # Errors
+    ///
+    /// If the initial skin or animation doesn't exist.
+    pub fn clone_instance(&self) -> Result<Self, SpineError> {
+        Self::from_skeleton_data(
+            self.atlas.clone(),
+            self.skeleton_data.clone(),
+            self.controller.settings.premultiplied_alpha,
+            self.options.clone(),
+            self.source.clone(),
+        )
+    }
+
+    /// Name of the skin currently used by the skeleton, if any.
+    fn skin_name(&self) -> Option<String> {
+        // SAFETY: The skin, if any, is owned by the skeleton data.
+        let skin = unsafe { (*self.controller.skeleton.c_ptr()).skin.as_ref() }?;
+        // SAFETY: Skins always have a name.
+        Some(
+            unsafe { CStr::from_ptr(skin.name) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    }
+
+    /// Copies the color, dark color and attachment of every slot into
+    /// `spine`, another instance of the same model.
+    #[cfg(all(feature = "wgpu", feature = "image"))]
+    fn copy_slots_into(&self, spine: &mut Spine) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut spine.controller)?;
+        // SAFETY: Both skeletons come from the same skeleton data, so they have
+        // the same slots, in the same order; and the attachments are owned by
+        // the skins of that data, which outlives both of them.
+        unsafe {
+            let source = &*self.controller.skeleton.c_ptr();
+            let target = &*controller.skeleton.c_ptr();
+            for index in 0..source.slotsCount.min(target.slotsCount) as usize {
+                let source_slot = *source.slots.add(index);
+                let target_slot = *target.slots.add(index);
+                (*target_slot).color = (*source_slot).color;
+                if let (Some(source_dark), Some(target_dark)) = (
+                    (*source_slot).darkColor.as_ref(),
+                    (*target_slot).darkColor.as_mut(),
+                ) {
+                    *target_dark = *source_dark;
+                }
+                rusty_spine::c::spSlot_setAttachment(target_slot, (*source_slot).attachment);
+            }
+        }
+        Ok(())
+    }
+
+    /// Loads the atlas and skeleton again, from the files the model was loaded
+    /// from; e.g. to preview them while they are being exported.
+    ///
+    /// The options are kept, and so are the skin and the animations playing
+    /// on every track (with their time, loop, speed, alpha, mix blend, and
+    /// the rest of their settings, e.g. whether they are reversed), as long
+    /// as they still exist; a skin that doesn't is logged, and the skin of the
+    /// options is used instead. Everything else starts over, e.g. queued
+    /// animations, mixes set with [`Spine::set_mix`] and changes made to the
+    /// pose.
+    ///
+    /// NOTE: Models loaded with [`Spine::with_atlas`] keep their atlas, and
+    /// only load their skeleton again.
+    ///
+    /// # Errors
+    ///
+    /// If any of the files can't be read or parsed, or if the initial skin or
+    /// animation of the options doesn't exist anymore; the model is left as it
+    /// was.
+    pub fn reload(&mut self) -> Result<(), LoadError> {
+        let atlas = match &self.source.atlas {
+            Some(path) => Self::load_atlas(path)?,
+            None => Arc::clone(&self.atlas),
+        };
+        let mut spine = Self::__with_atlas(
+            atlas,
+            self.source.atlas.as_deref(),
+            self.source.skeleton.as_ref(),
+            self.options.clone(),
+        )?;
+
+        if let Some(skin) = self.skin_name()
+            && let Err(error) = spine.set_skin(&skin)
+        {
+            log::warn!("Couldn't restore the skin of the reloaded Spine model: {error}");
+        }
+
+        let controller = unique_controller(&mut spine.controller).map_err(LoadError::Options)?;
+        controller.animation_state.clear_tracks();
+        for track in self.controller.animation_state.tracks().flatten() {
+            let track_index = track.track_index();
+            let Ok(mut new_track) = controller.animation_state.set_animation_by_name(
+                track_index,
+                track.animation().name(),
+                track.looping(),
+            ) else {
+                continue;
+            };
+            new_track.set_track_time(track.track_time());
+            new_track.set_timescale(track.timescale());
+            new_track.set_alpha(track.alpha());
+            new_track.set_reverse(track.reverse());
+            new_track.set_hold_previous(track.hold_previous());
+            new_track.set_shortest_rotation(track.shortest_rotation());
+            new_track.set_event_threshold(track.event_threshold());
+            new_track.set_alpha_attachment_threshold(track.alpha_attachment_threshold());
+            new_track.set_mix_attachment_threshold(track.mix_attachment_threshold());
+            new_track.set_mix_draw_order_threshold(track.mix_draw_order_threshold());
+            // SAFETY: Both pointers come from live track entries; `rusty_spine`
+            // has no accessor for this field.
+            unsafe { (*new_track.c_ptr()).mixBlend = (*track.c_ptr()).mixBlend };
+        }
+        controller.update(0.0, self.options.physics.into_spine());
+        // NOTE: The animations were only restored, not started.
+        spine.events.take();
+
+        spine.paused = self.paused;
+        spine.queued_events = std::mem::take(&mut self.queued_events);
+        *self = spine;
+        Ok(())
+    }
+
+    /// The files the model was loaded from, e.g. to watch them for changes
+    /// and [`Spine::reload`] it.
+    ///
+    /// NOTE: Models loaded with [`Spine::with_atlas`] don't know where their
+    /// atlas (nor its texture pages) was loaded from.
+    pub fn source_paths(&self) -> SourcePaths {
+        // NOTE: The runtime joins the pages to the directory of the atlas.
+        let textures = match &self.source.atlas {
+            Some(atlas) => {
+                let directory = atlas.parent().unwrap_or(Path::new(""));
+                self.atlas
+                    .pages()
+                    .map(|page| directory.join(page.name()))
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        SourcePaths {
+            atlas: self.source.atlas.clone(),
+            skeleton: self.source.skeleton.path().to_owned(),
+            textures,
+        }
+    }
 }
 
-impl Spine {
-    pub fn new<A, S>(
-        atlas: A,
-        skel: SkeletonKind<S>,
-        options: SpineOptions,
-    ) -> Result<Self, SpineError>
-    where
-        A: AsRef<Path>,
-        S: AsRef<Path>,
-    {
-        Self::__new(atlas.as_ref(), skel.as_ref(), options)
+impl Spine {
+    pub fn options(&self) -> &SpineOptions {
+        &self.options
+    }
+
+    /// Iterator over all the animations available in the loaded skeleton.
+    pub fn animations(&self) -> impl Iterator<Item = AnimationInfo> + '_ {
+        self.skeleton_data
+            .animations()
+            .enumerate()
+            .map(|(index, animation)| AnimationInfo {
+                name: animation.name().to_owned(),
+                index,
+                duration: animation.duration(),
+            })
+    }
+
+    /// Iterator over the names of all the skins available in the loaded
+    /// skeleton, which can be used with [`Spine::set_skin`].
+    ///
+    /// NOTE: This includes the default skin, which holds the attachments not
+    /// assigned to any skin, and is always named `"default"` by the editor.
+    ///
+    /// NOTE: The names are owned because the Spine runtime only lends each
+    /// skin for as long as the iterator yields it, like for
+    /// [`Spine::animations`].
+    pub fn skins(&self) -> impl Iterator<Item = String> + '_ {
+        self.skeleton_data
+            .skins()
+            .map(|skin| skin.name().to_owned())
+    }
+
+    /// Whether any slot of the loaded skeleton uses two color tinting, i.e.
+    /// whether [`Vertex::dark_color`] is ever used.
+    pub fn has_two_color_tint(&self) -> bool {
+        self.skeleton_data
+            .slots()
+            .any(|slot| slot.dark_color().is_some())
+    }
+
+    /// Information about every page of the loaded atlas, in the order they
+    /// are listed in the atlas file.
+    pub fn atlas_pages(&self) -> Vec<PageInfo> {
+        self.atlas
+            .pages()
+            .map(|page| PageInfo {
+                name: page.name().to_owned(),
+                width: page.width().max(0) as u32,
+                height: page.height().max(0) as u32,
+                min_filter: page.min_filter(),
+                mag_filter: page.mag_filter(),
+                u_wrap: page.u_wrap(),
+                v_wrap: page.v_wrap(),
+                premultiplied_alpha: page.pma(),
+            })
+            .collect()
+    }
+
+    /// Sets the animation of `track` by name, replacing whatever was playing.
+    ///
+    /// # Errors
+    ///
+    /// If the animation doesn't exist, or if the model is still being
+    /// rendered.
+    pub fn set_animation_by_name(
+        &mut self,
+        track: usize,
+        name: &str,
+        loop_: bool,
+    ) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        controller
+            .animation_state
+            .set_animation_by_name(track, name, loop_)?;
+        Ok(())
+    }
+
+    /// Sets the animation of `track` by index, replacing whatever was playing.
+    ///
+    /// # Errors
+    ///
+    /// If the animation doesn't exist, or if the model is still being
+    /// rendered.
+    pub fn set_animation_by_index(
+        &mut self,
+        track: usize,
+        index: usize,
+        loop_: bool,
+    ) -> Result<(), SpineError> {
+        let Some(animation) = self.skeleton_data.animation_at_index(index) else {
+            return Err(SpineError::NotFound {
+                what: "Animation".to_owned(),
+                name: index.to_string(),
+            });
+        };
+        let controller = unique_controller(&mut self.controller)?;
+        controller
+            .animation_state
+            .set_animation(track, &animation, loop_);
+        Ok(())
+    }
+
+    /// Queues an animation by name, to be played after the current (or last
+    /// queued) animation of `track`.
+    ///
+    /// `delay` is the number of seconds, after the previous animation started,
+    /// to start playing this one. If `<= 0`, it is relative to when the
+    /// previous animation completes (minus any mix duration), so `0` starts it
+    /// right after the previous animation finishes.
+    ///
+    /// # Errors
+    ///
+    /// If the animation doesn't exist, or if the model is still being
+    /// rendered.
+    pub fn add_animation_by_name(
+        &mut self,
+        track: usize,
+        name: &str,
+        loop_: bool,
+        delay: f32,
+    ) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        controller
+            .animation_state
+            .add_animation_by_name(track, name, loop_, delay)?;
+        Ok(())
+    }
+
+    /// Fades out whatever is playing on `track` over `mix_duration` seconds,
+    /// by mixing it into an empty animation.
+    ///
+    /// # Errors
+    ///
+    /// If the model is still being rendered.
+    pub fn set_empty_animation(
+        &mut self,
+        track: usize,
+        mix_duration: f32,
+    ) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        controller
+            .animation_state
+            .set_empty_animation(track, mix_duration);
+        Ok(())
+    }
+
+    /// Queues an empty animation on `track`, to fade out the current (or last
+    /// queued) animation over `mix_duration` seconds.
+    ///
+    /// `delay` works like in [`Spine::add_animation_by_name`].
+    ///
+    /// # Errors
+    ///
+    /// If the model is still being rendered.
+    pub fn add_empty_animation(
+        &mut self,
+        track: usize,
+        mix_duration: f32,
+        delay: f32,
+    ) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        controller
+            .animation_state
+            .add_empty_animation(track, mix_duration, delay);
+        Ok(())
+    }
+
+    /// Removes the current and queued animations of `track`, without mixing
+    /// them out.
+    ///
+    /// NOTE: The skeleton keeps the pose the animations left it in; see
+    /// [`Spine::set_empty_animation`] to fade them out instead.
+    ///
+    /// # Errors
+    ///
+    /// If the model is still being rendered.
+    pub fn clear_track(&mut self, track: usize) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        controller.animation_state.clear_track(track);
+        Ok(())
+    }
+
+    /// Removes the animations of every track; see [`Spine::clear_track`].
+    ///
+    /// # Errors
+    ///
+    /// If the model is still being rendered.
+    pub fn clear_tracks(&mut self) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        controller.animation_state.clear_tracks();
+        Ok(())
+    }
+
+    /// Current time, in seconds, of the animation playing on `track`.
+    ///
+    /// For looping animations this keeps growing past the animation duration.
+    pub fn track_time(&self, track: usize) -> Option<f32> {
+        let animation_state = &self.controller.animation_state;
+        animation_state
+            .get_current(track)
+            .map(|track_entry| track_entry.track_time())
+    }
+
+    /// Progress of the animation playing on `track`, from `0.0` to `1.0`.
+    ///
+    /// For looping animations it wraps back to `0.0` on every loop; animations
+    /// without any duration are always `1.0`.
+    pub fn progress(&self, track: usize) -> Option<f32> {
+        let track_entry = self.controller.animation_state.get_current(track)?;
+        let duration = track_entry.animation().duration();
+        let time = track_entry.track_time();
+        let progress = match track_entry.looping() {
+            _ if duration <= 0.0 => 1.0,
+            true => time.rem_euclid(duration) / duration,
+            false => (time / duration).clamp(0.0, 1.0),
+        };
+        Some(progress)
+    }
+
+    /// Animation playing on `track`.
+    ///
+    /// Returns `None` if the track is empty, or if it is playing an empty
+    /// animation.
+    pub fn current_animation(&self, track: usize) -> Option<AnimationInfo> {
+        let track_entry = self.controller.animation_state.get_current(track)?;
+        let name = track_entry.animation().name().to_owned();
+        self.animations().find(|animation| animation.name == name)
+    }
+
+    /// Whether the non looping animation playing on `track` reached its end.
+    ///
+    /// Returns `false` if the track is empty or looping.
+    pub fn is_complete(&self, track: usize) -> bool {
+        let animation_state = &self.controller.animation_state;
+        animation_state
+            .get_current(track)
+            .is_some_and(|track_entry| {
+                !track_entry.looping() && track_entry.track_time() >= track_entry.track_complete()
+            })
+    }
+
+    /// Seeks the animation playing on `track` to `time` seconds.
+    ///
+    /// The time is wrapped to the animation duration if the track loops, and
+    /// clamped to it otherwise.
+    ///
+    /// # Errors
+    ///
+    /// If the track is empty, or if the model is still being rendered.
+    pub fn set_track_time(&mut self, track: usize, time: f32) -> Result<(), SpineError> {
+        let mut track_entry = self.track_mut(track)?;
+        let duration = track_entry.animation().duration();
+        let time = match track_entry.looping() {
+            true if duration > 0.0 => time.rem_euclid(duration),
+            _ => time.clamp(0.0, duration),
+        };
+        track_entry.set_track_time(time);
+        Ok(())
+    }
+
+    /// Sets the speed multiplier of the animation playing on `track`.
+    ///
+    /// It is multiplied with [`Animation::playback_speed`], which applies to
+    /// every track; e.g. a timescale of `0.5` with a playback speed of `2.0`
+    /// plays the track at its normal speed. Negative values are clamped to
+    /// `0.0`. The timescale belongs to the current animation, so setting a new
+    /// animation on the track resets it to `1.0`.
+    ///
+    /// # Errors
+    ///
+    /// If the track is empty, or if the model is still being rendered.
+    pub fn set_track_timescale(&mut self, track: usize, scale: f32) -> Result<(), SpineError> {
+        let mut track_entry = self.track_mut(track)?;
+        track_entry.set_timescale(scale.max(0.0));
+        Ok(())
+    }
+
+    /// Sets how much the animation playing on `track` affects the pose of the
+    /// lower tracks, from `0.0` to `1.0`.
+    ///
+    /// Like the timescale, it resets to `1.0` when a new animation is set on
+    /// the track. It doesn't make sense on track `0`, unless its
+    /// [`MixBlend`] is [`MixBlend::Setup`].
+    ///
+    /// # Errors
+    ///
+    /// If the track is empty, or if the model is still being rendered.
+    pub fn set_track_alpha(&mut self, track: usize, alpha: f32) -> Result<(), SpineError> {
+        let mut track_entry = self.track_mut(track)?;
+        track_entry.set_alpha(alpha.clamp(0.0, 1.0));
+        Ok(())
+    }
+
+    /// Sets how the animation playing on `track` is mixed with the pose of the
+    /// lower tracks; e.g. [`MixBlend::Add`] for additive layers.
+    ///
+    /// # Errors
+    ///
+    /// If the track is empty, or if the model is still being rendered.
+    pub fn set_track_mix_blend(&mut self, track: usize, blend: MixBlend) -> Result<(), SpineError> {
+        let track_entry = self.track_mut(track)?;
+        // SAFETY: The pointer comes from a live track entry, which is mutably
+        // borrowed; `rusty_spine` has no accessor for this field.
+        unsafe { (*track_entry.c_ptr()).mixBlend = blend.into_c() };
+        Ok(())
+    }
+
+    /// Sets the skin used by the skeleton.
+    ///
+    /// # Errors
+    ///
+    /// If the skin doesn't exist, or if the model is still being rendered.
+    pub fn set_skin(&mut self, name: &str) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        controller.skeleton.set_skin_by_name(name)?;
+        controller.skeleton.set_slots_to_setup_pose();
+        Ok(())
+    }
+
+    /// Sets the crossfade duration, in seconds, used when switching from the
+    /// `from` animation to the `to` animation.
+    ///
+    /// # Errors
+    ///
+    /// If any of the animations doesn't exist, or if the model is still being
+    /// rendered.
+    pub fn set_mix(&mut self, from: &str, to: &str, duration: f32) -> Result<(), SpineError> {
+        for name in [from, to] {
+            if self.skeleton_data.find_animation(name).is_none() {
+                return Err(SpineError::NotFound {
+                    what: "Animation".to_owned(),
+                    name: name.to_owned(),
+                });
+            }
+        }
+        let controller = unique_controller(&mut self.controller)?;
+        controller
+            .animation_state
+            .data_mut()
+            .set_mix_by_name(from, to, duration);
+        Ok(())
+    }
+
+    /// See [`Animation::playback_speed`].
+    pub fn set_playback_speed(&mut self, speed: f32) {
+        self.options.animation.playback_speed = speed;
+    }
+
+    /// Freezes the skeleton in its current pose; it is still drawn.
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// See [`SpineOptions::physics`].
+    pub fn set_physics(&mut self, physics: Physics) {
+        self.options.physics = physics;
+    }
+
+    /// Resets the physics constraints to the current pose, so that they settle
+    /// right away instead of springing after a jump, e.g. when seeking with
+    /// [`Spine::set_track_time`] or moving the skeleton itself.
+    ///
+    /// NOTE: Changing the [`Scene`] only changes how the skeleton is projected,
+    /// so it never affects physics.
+    ///
+    /// # Errors
+    ///
+    /// If the model is still being rendered.
+    pub fn reset_physics(&mut self) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        controller
+            .skeleton
+            .update_world_transform(rusty_spine::Physics::Reset);
+        Ok(())
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// What the widget drew on its last frame, to profile how many models
+    /// can be shown at once.
+    pub fn draw_stats(&self) -> DrawStats {
+        self.draw_stats
+    }
+
+    /// Axis-aligned bounds, in model space, of the skeleton's current pose.
+    ///
+    /// Returns `None` if no attachment is visible.
+    pub fn bounds(&self) -> Option<egui::Rect> {
+        skeleton_bounds(&self.controller.skeleton)
+    }
+
+    /// Hash of the current pose, to assert in tests that a model still poses
+    /// the same after advancing it by fixed steps.
+    ///
+    /// The hash is 64-bit FNV-1a, over the bits (in little endian) of the
+    /// vertices, triangle indices, colors and dark colors of every mesh, as
+    /// the widget would draw them (i.e. tinted by [`SpineOptions::tint`]).
+    ///
+    /// NOTE: It is stable across platforms, but a different version of the
+    /// Spine runtime may pose the same skeleton slightly differently.
+    ///
+    /// NOTE: It takes `&mut self` because the runtime builds the meshes with the
+    /// skeleton's own clipper and vertex buffers, so it can't run while a
+    /// frame still holds the model.
+    ///
+    /// # Errors
+    ///
+    /// If the model is still being rendered.
+    pub fn pose_hash(&mut self) -> Result<u64, SpineError> {
+        const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const PRIME: u64 = 0x0100_0000_01b3;
+
+        let controller = unique_controller(&mut self.controller)?;
+        let mut renderables = controller.combined_renderables();
+        apply_tint(&mut renderables, self.options.tint);
+
+        let mut hash = OFFSET_BASIS;
+        let mut write = |bytes: &[u8]| {
+            for &byte in bytes {
+                hash = (hash ^ u64::from(byte)).wrapping_mul(PRIME);
+            }
+        };
+        for renderable in &renderables {
+            let floats = renderable
+                .vertices
+                .iter()
+                .flatten()
+                .chain(renderable.colors.iter().flatten())
+                .chain(renderable.dark_colors.iter().flatten());
+            for float in floats {
+                write(&float.to_bits().to_le_bytes());
+            }
+            for index in &renderable.indices {
+                write(&index.to_le_bytes());
+            }
+        }
+        Ok(hash)
+    }
+
+    /// Whether `point`, in screen space, is over one of the attachments drawn
+    /// when the skeleton is painted into `rect`.
+    pub fn hit_test(&self, point: egui::Pos2, rect: egui::Rect) -> bool {
+        self.hit_slot(point, rect).is_some()
+    }
+
+    /// Name of the top-most slot whose attachment is under `point`, in screen
+    /// space, when the skeleton is painted into `rect`.
+    pub fn hit_slot(&self, point: egui::Pos2, rect: egui::Rect) -> Option<String> {
+        let model = self.screen_to_model(point, rect);
+        let skeleton = &self.controller.skeleton;
+        if self.options.tint.a() == 0 || skeleton.color().a <= 0. {
+            return None;
+        }
+
+        let mut hit = None;
+        for_each_visible_attachment(skeleton, |slot, world_vertices, indices| {
+            // NOTE: Fully transparent attachments are drawn, but can't be
+            // seen, so they shouldn't catch the pointer.
+            if slot.color().a <= 0. {
+                return;
+            }
+            let vertex = |index: u16| {
+                let index = usize::from(index) * 2;
+                Vec2::new(world_vertices[index], world_vertices[index + 1])
+            };
+            let is_hit = indices.chunks_exact(3).any(|triangle| {
+                triangle_contains(
+                    model,
+                    [
+                        vertex(triangle[0]),
+                        vertex(triangle[1]),
+                        vertex(triangle[2]),
+                    ],
+                )
+            });
+            if is_hit {
+                hit = Some(slot.data().name().to_owned());
+            }
+        });
+        hit
+    }
+
+    /// World position, in model space, of `bone` as of the last update.
+    pub fn bone_world_position(&self, bone: &str) -> Option<Vec2> {
+        let bone = self.controller.skeleton.find_bone(bone)?;
+        Some(Vec2::new(bone.world_x(), bone.world_y()))
+    }
+
+    /// Screen position of `bone`, as of the last update, when the skeleton is
+    /// painted into `rect`.
+    pub fn bone_screen_position(&self, bone: &str, rect: egui::Rect) -> Option<egui::Pos2> {
+        let position = self.bone_world_position(bone)?;
+        Some(self.model_to_screen(position, rect))
+    }
+
+    /// Sets the local rotation of `bone`, in degrees.
+    ///
+    /// NOTE: Animations keying the rotation of the bone overwrite it on the
+    /// next update.
+    ///
+    /// # Errors
+    ///
+    /// If the bone doesn't exist, or if the model is still being rendered.
+    pub fn set_bone_rotation(&mut self, bone: &str, degrees: f32) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        let mut bone =
+            controller
+                .skeleton
+                .find_bone_mut(bone)
+                .ok_or_else(|| SpineError::NotFound {
+                    what: "Bone".to_owned(),
+                    name: bone.to_owned(),
+                })?;
+        bone.set_rotation(degrees);
+        Ok(())
+    }
+
+    /// Information about every slot of the skeleton, in draw order, from the
+    /// back to the front.
+    pub fn slots(&self) -> Vec<SlotInfo> {
+        self.controller
+            .skeleton
+            .draw_order()
+            .map(|slot| {
+                let color = slot.color();
+                let [r, g, b, a] = [color.r, color.g, color.b, color.a]
+                    .map(|c| (c.clamp(0., 1.) * 255.).round() as u8);
+                SlotInfo {
+                    name: slot.data().name().to_owned(),
+                    attachment: slot
+                        .attachment()
+                        .map(|attachment| attachment.name().to_owned()),
+                    color: egui::Color32::from_rgba_unmultiplied(r, g, b, a),
+                }
+            })
+            .collect()
+    }
+
+    /// Moves the target bone of the IK `constraint` to `target`, in the
+    /// coordinates of the skeleton (see [`Spine::screen_to_model`]).
+    ///
+    /// NOTE: Animations keying the translation of the target bone overwrite
+    /// it on the next update.
+    ///
+    /// # Errors
+    ///
+    /// If the IK constraint doesn't exist, or if the model is still being
+    /// rendered.
+    pub fn set_ik_target(&mut self, constraint: &str, target: Vec2) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        let skeleton = &mut controller.skeleton;
+        // NOTE: Root bones are only transformed by the skeleton itself, whose
+        // scale includes the flips of the scene.
+        let y_down = if Bone::is_y_down() { -1. } else { 1. };
+        let from_skeleton = (Affine2::from_translation(vec2(skeleton.x(), skeleton.y()))
+            * Affine2::from_scale(vec2(skeleton.scale_x(), skeleton.scale_y() * y_down)))
+        .inverse();
+        let mut constraint =
+            skeleton
+                .find_ik_constraint_mut(constraint)
+                .ok_or_else(|| SpineError::NotFound {
+                    what: "IK constraint".to_owned(),
+                    name: constraint.to_owned(),
+                })?;
+        let mut bone = constraint.target_mut();
+        let (local_x, local_y) = match bone.parent() {
+            Some(parent) => parent.world_to_local(target.x, target.y),
+            None => from_skeleton.transform_point2(target).into(),
+        };
+        bone.set_x(local_x);
+        bone.set_y(local_y);
+        Ok(())
+    }
+
+    /// Sets every mix of the transform `constraint` to `mix`, from `0.0` (the
+    /// constrained bones are left untouched) to `1.0` (they fully follow the
+    /// target).
+    ///
+    /// NOTE: Animations keying the mixes of the constraint overwrite them on
+    /// the next update.
+    ///
+    /// # Errors
+    ///
+    /// If the transform constraint doesn't exist, or if the model is still
+    /// being rendered.
+    pub fn set_transform_constraint_mix(
+        &mut self,
+        constraint: &str,
+        mix: f32,
+    ) -> Result<(), SpineError> {
+        self.set_transform_constraint_mixes(constraint, TransformMix::splat(mix))
+    }
+
+    /// Sets each mix of the transform `constraint` separately; see
+    /// [`Spine::set_transform_constraint_mix`].
+    ///
+    /// # Errors
+    ///
+    /// If the transform constraint doesn't exist, or if the model is still
+    /// being rendered.
+    pub fn set_transform_constraint_mixes(
+        &mut self,
+        constraint: &str,
+        mix: TransformMix,
+    ) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        let mut constraint = controller
+            .skeleton
+            .find_transform_constraint_mut(constraint)
+            .ok_or_else(|| SpineError::NotFound {
+                what: "Transform constraint".to_owned(),
+                name: constraint.to_owned(),
+            })?;
+        constraint.set_mix_rotate(mix.rotate);
+        constraint.set_mix_x(mix.x);
+        constraint.set_mix_y(mix.y);
+        constraint.set_mix_scale_x(mix.scale_x);
+        constraint.set_mix_scale_y(mix.scale_y);
+        // NOTE: The setter of `mixShearY` lacks the `mix_` prefix.
+        constraint.set_shear_y(mix.shear_y);
+        Ok(())
+    }
+
+    /// Tints the attachment of `slot` with `color`.
+    ///
+    /// NOTE: Animations keying the color of the slot overwrite it on the next
+    /// update.
+    ///
+    /// # Errors
+    ///
+    /// If the slot doesn't exist, or if the model is still being rendered.
+    pub fn set_slot_color(&mut self, slot: &str, color: egui::Color32) -> Result<(), SpineError> {
+        let [r, g, b, a] = color.to_srgba_unmultiplied().map(|c| f32::from(c) / 255.);
+        let mut slot = self.slot_mut(slot)?;
+        slot.color_mut().set_from_floats(r, g, b, a);
+        Ok(())
+    }
+
+    /// Sets the dark color of `slot`, used by two color tinting to tint the
+    /// darker parts of its attachment; its alpha is ignored.
+    ///
+    /// NOTE: Animations keying the color of the slot overwrite it on the next
+    /// update.
+    ///
+    /// # Errors
+    ///
+    /// If the slot doesn't exist, if it doesn't use two color tinting (which
+    /// is enabled per slot in the Spine editor), or if the model is still being
+    /// rendered.
+    pub fn set_slot_dark_color(
+        &mut self,
+        slot: &str,
+        color: egui::Color32,
+    ) -> Result<(), SpineError> {
+        let [r, g, b, _] = color.to_srgba_unmultiplied().map(|c| f32::from(c) / 255.);
+        let spine_slot = self.slot_mut(slot)?;
+        // NOTE: `rusty_spine` only exposes the dark color for reading. It is
+        // only allocated for slots that use two color tinting, and the runtime
+        // restores it from the slot data, so it can't be added to other slots.
+        // SAFETY: The pointer is either null or owned by the slot, which is
+        // mutably borrowed.
+        let Some(dark_color) = (unsafe { (*spine_slot.c_ptr()).darkColor.as_mut() }) else {
+            return Err(SpineError::NotFound {
+                what: "Slot dark color".to_owned(),
+                name: slot.to_owned(),
+            });
+        };
+        (dark_color.r, dark_color.g, dark_color.b, dark_color.a) = (r, g, b, 1.0);
+        Ok(())
+    }
+
+    /// Restores the setup pose color of `slot`.
+    ///
+    /// # Errors
+    ///
+    /// If the slot doesn't exist, or if the model is still being rendered.
+    pub fn reset_slot_color(&mut self, slot: &str) -> Result<(), SpineError> {
+        let mut slot = self.slot_mut(slot)?;
+        let color = slot.data().color();
+        slot.color_mut().set_from_color(&color);
+        Ok(())
+    }
+
+    /// Sets the attachment shown by `slot`, looked up in the current skin (or
+    /// the default skin); `None` hides the slot.
+    ///
+    /// NOTE: Animations keying the attachment of the slot overwrite it on the
+    /// next update.
+    ///
+    /// # Errors
+    ///
+    /// If the slot or the attachment don't exist, or if the model is still
+    /// being rendered.
+    pub fn set_attachment(
+        &mut self,
+        slot: &str,
+        attachment: Option<&str>,
+    ) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        if controller.skeleton.find_slot(slot).is_none() {
+            return Err(SpineError::NotFound {
+                what: "Slot".to_owned(),
+                name: slot.to_owned(),
+            });
+        }
+        if !controller.skeleton.set_attachment(slot, attachment) {
+            return Err(SpineError::NotFound {
+                what: "Attachment".to_owned(),
+                name: attachment.unwrap_or_default().to_owned(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Meshes of the current pose, as the widget would draw them, e.g. to
+    /// render the skeleton offscreen with a custom renderer.
+    ///
+    /// Like [`Spine::pose_hash`], it needs `&mut self` to build the meshes.
+    ///
+    /// # Errors
+    ///
+    /// If the model is still being rendered.
+    pub fn snapshot_meshes(&mut self) -> Result<Vec<RenderableMesh>, SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        let mut renderables = controller.combined_renderables();
+        apply_tint(&mut renderables, self.options.tint);
+
+        let meshes = renderables
+            .iter()
+            .map(|renderable| {
+                let texture_page = renderable.attachment_renderer_object.and_then(|object| {
+                    self.atlas.pages().find_map(|page| {
+                        // SAFETY: The page is alive while the atlas is.
+                        let page_object = unsafe { (*page.c_ptr()).rendererObject };
+                        std::ptr::eq(page_object, object).then(|| page.name().to_owned())
+                    })
+                });
+                RenderableMesh {
+                    vertices: Vertex::from_renderable(renderable),
+                    indices: renderable.indices.clone(),
+                    blend_mode: renderable.blend_mode,
+                    premultiplied_alpha: renderable.premultiplied_alpha,
+                    texture_page,
+                }
+            })
+            .collect();
+        Ok(meshes)
+    }
+
+    /// The underlying controller, for anything not covered by this wrapper.
+    pub fn controller(&self) -> &SkeletonController {
+        &self.controller
+    }
+
+    /// Mutable access to the underlying controller, for anything not covered by
+    /// this wrapper.
+    ///
+    /// Returns `None` if the model is still being rendered. Changes made to
+    /// the skeleton (e.g. moving bones) aren't reflected on the frame that was
+    /// already painted, only once the widget is shown again.
+    pub fn controller_mut(&mut self) -> Option<&mut SkeletonController> {
+        Arc::get_mut(&mut self.controller)
+    }
+
+    /// The scene the skeleton is drawn with; its setters can be chained, e.g.
+    /// `spine.scene_mut().set_scale(1.1).set_angle(0.5)`.
+    pub fn scene_mut(&mut self) -> &mut Scene {
+        &mut self.options.scene
+    }
+
+    /// Calls `callback` with the events of `kinds` emitted on `track` (or on
+    /// every track, when `None`), replacing [`SpineOptions::event_cb`].
+    ///
+    /// NOTE: The filter only applies to `callback`; [`Spine::drain_events`]
+    /// still takes every event.
+    pub fn on_event<F>(&mut self, track: Option<usize>, kinds: EventKinds, callback: F)
+    where
+        F: FnMut(SpineEvent) + Send + 'static,
+    {
+        let callback = EventCallback::new(callback).with_filter(EventFilter { track, kinds });
+        self.options.event_cb = Some(callback);
+        self.events.set_filter(self.options.listened_events());
+    }
+
+    /// Takes the events emitted since the last call, in the order they were
+    /// emitted; a polling alternative to [`SpineOptions::event_cb`].
+    ///
+    /// NOTE: Events are only kept while [`SpineOptions::queue_events`] is set.
+    pub fn drain_events(&mut self) -> Vec<SpineEvent> {
+        std::mem::take(&mut self.queued_events)
+    }
+
+    /// Advances the animations by `dt` seconds, and calls
+    /// [`SpineOptions::event_cb`] with the emitted events.
+    ///
+    /// `dt` is scaled by [`Animation::playback_speed`], and ignored while
+    /// paused. The widget already calls this every frame, so it is only needed
+    /// with [`TimeStep::Manual`], or when the skeleton is drawn by other means.
+    ///
+    /// # Errors
+    ///
+    /// If the model is still being rendered.
+    pub fn update(&mut self, dt: f32) -> Result<(), SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+
+        let playback_speed = self.options.animation.playback_speed.max(0.0);
+        // NOTE: The controller is still updated while paused, so that changes
+        // made to the skeleton (e.g. a new animation) are reflected.
+        let dt = match self.paused {
+            true => 0.0,
+            false => dt * playback_speed,
+        };
+        let scene = &self.options.scene;
+        let skeleton = &mut controller.skeleton;
+        let sign = |flip| if flip { -1. } else { 1. };
+        skeleton.set_scale_x(skeleton.scale_x().abs() * sign(scene.flip_x));
+        skeleton.set_scale_y(skeleton.scale_y().abs() * sign(scene.flip_y));
+        controller.update(dt, self.options.physics.into_spine());
+
+        self.dispatch_events();
+        Ok(())
+    }
+
+    /// Whether the pose could change on the next update done by the widget.
+    ///
+    /// NOTE: Physics constraints that are still settling after all animations
+    /// completed aren't taken into account.
+    #[cfg(any(feature = "wgpu", feature = "glow"))]
+    fn is_animating(&self) -> bool {
+        if self.paused
+            || self.options.animation.playback_speed <= 0.0
+            || self.options.time_step == TimeStep::Manual
+        {
+            return false;
+        }
+
+        let animation_state = &self.controller.animation_state;
+        animation_state.tracks().flatten().any(|track_entry| {
+            track_entry.looping()
+                || track_entry.next().is_some()
+                || track_entry.mixing_from().is_some()
+                || track_entry.track_time() < track_entry.track_complete()
+        })
+    }
+
+    #[cfg(any(feature = "wgpu", feature = "glow"))]
+    fn cull_mode(&self) -> Option<Face> {
+        let cull_mode = self.options.animation.cull_mode;
+        // NOTE: Flipping the skeleton on a single axis reverses the winding of
+        // its triangles.
+        match self.options.scene.flip_x != self.options.scene.flip_y {
+            true => cull_mode.map(Face::flipped),
+            false => cull_mode,
+        }
+    }
+
+    /// Loads and uploads the textures of the model, and creates the pipelines
+    /// drawing them, blocking until they are ready.
+    ///
+    /// Otherwise that work is done while the model is drawn, which could make
+    /// it pop in a few frames after it is first added to the ui.
+    ///
+    /// # Panics
+    ///
+    /// If the spine context wasn't initialized with
+    /// [`init_wgpu_spine_context`].
+    #[cfg(feature = "wgpu")]
+    pub fn preload(&self, render_state: &egui_wgpu::RenderState) {
+        renderer::wgpu::preload(
+            render_state,
+            &self.atlas,
+            self.controller.settings.premultiplied_alpha,
+            self.cull_mode(),
+        );
+    }
+
+    /// Draws the current pose into an image of `size` pixels, with a
+    /// transparent background, as if the widget was painted into a rect of
+    /// that many points.
+    ///
+    /// Like on [`Spine::preload`], the textures still loading are waited for.
+    /// Like [`Spine::pose_hash`], it needs `&mut self` to build the meshes.
+    ///
+    /// # Errors
+    ///
+    /// If the model is still being rendered.
+    ///
+    /// # Panics
+    ///
+    /// If the spine context wasn't initialized with
+    /// [`init_wgpu_spine_context`].
+    #[cfg(all(feature = "wgpu", feature = "image"))]
+    pub fn render_to_image(
+        &mut self,
+        render_state: &egui_wgpu::RenderState,
+        size: (u32, u32),
+    ) -> Result<image::RgbaImage, SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        let mut renderables = controller.combined_renderables();
+        apply_tint(&mut renderables, self.options.tint);
+        let meshes = Meshes::new(Arc::clone(&self.controller), renderables);
+
+        let (width, height) = (size.0.max(1), size.1.max(1));
+        let size = egui::vec2(width as f32, height as f32);
+        let scene_view = self.scene_view(size);
+        let outline = self
+            .options
+            .outline
+            .and_then(|outline| outline.pass(size, scene_view, self.options.color_space));
+        let callback = RendererCallback::new(
+            meshes,
+            scene_view,
+            self.cull_mode(),
+            self.options.desaturate,
+            self.options.scene.depth,
+            outline,
+        );
+        let pixels = renderer::wgpu::render_offscreen(render_state, &callback, width, height);
+        debug_assert_eq!(pixels.len(), width as usize * height as usize * 4);
+        Ok(image::RgbaImage::from_raw(width, height, pixels)
+            .expect("render_offscreen should return 4 bytes per pixel"))
+    }
+
+    /// Draws `frames` evenly spaced frames of `animation`, starting from its
+    /// first one, into images of `size` pixels; see [`Spine::render_to_image`].
+    ///
+    /// The frames are drawn by another instance of the model (see
+    /// [`Spine::clone_instance`]), so the current pose, tracks and events of
+    /// this one are left untouched. That instance still uses the current skin,
+    /// and the current color, dark color and attachment of every slot, unless
+    /// `animation` keys them. Physics are posed instead of simulated.
+    ///
+    /// # Errors
+    ///
+    /// If the animation doesn't exist.
+    ///
+    /// # Panics
+    ///
+    /// If the spine context wasn't initialized with
+    /// [`init_wgpu_spine_context`].
+    #[cfg(all(feature = "wgpu", feature = "image"))]
+    pub fn render_animation_frames(
+        &self,
+        render_state: &egui_wgpu::RenderState,
+        animation: &str,
+        frames: usize,
+        size: (u32, u32),
+    ) -> Result<Vec<image::RgbaImage>, SpineError> {
+        let mut spine = self.clone_instance()?;
+        spine.options.event_cb = None;
+        spine.options.physics = Physics::Pose;
+        spine.options.animation.playback_speed = 1.0;
+        if let Some(skin) = self.skin_name() {
+            spine.set_skin(&skin)?;
+        }
+        spine.clear_tracks()?;
+        spine.set_animation_by_name(0, animation, false)?;
+        if let Some(controller) = spine.controller_mut() {
+            controller.skeleton.set_to_setup_pose();
+        }
+        self.copy_slots_into(&mut spine)?;
+
+        let duration = spine.current_animation(0).map_or(0.0, |info| info.duration);
+        (0..frames)
+            .map(|frame| {
+                spine.set_track_time(0, duration * frame as f32 / frames as f32)?;
+                spine.update(0.0)?;
+                spine.render_to_image(render_state, size)
+            })
+            .collect()
+    }
+
+    fn scene_view(&self, size: egui::Vec2) -> Mat4 {
+        let bounds = match self.options.scene.fit {
+            Some(_) => skeleton_bounds(&self.controller.skeleton),
+            None => None,
+        };
+        self.options.scene.create_scene_view(size, bounds)
+    }
+
+    /// The projection the skeleton is drawn with into a rect of `size` points;
+    /// see [`Scene::view_matrix`], which this completes with [`Scene::fit`].
+    pub fn view_matrix(&self, size: egui::Vec2) -> Mat4 {
+        self.scene_view(size)
+    }
+
+    /// Converts `point`, in screen coordinates, to the coordinates of the
+    /// skeleton when it is painted into `rect`.
+    pub fn screen_to_model(&self, point: egui::Pos2, rect: egui::Rect) -> Vec2 {
+        let ndc = (point - rect.center()) / (rect.size() * 0.5);
+        let model = self
+            .scene_view(rect.size())
+            .inverse()
+            .project_point3(vec3(ndc.x, -ndc.y, 0.));
+        model.truncate()
+    }
+
+    /// Converts `point`, in the coordinates of the skeleton (e.g. a bone's
+    /// world position), to screen coordinates when it is painted into `rect`;
+    /// the inverse of [`Spine::screen_to_model`].
+    ///
+    /// Both use the same projection the skeleton is drawn with, so they
+    /// account for the whole [`Scene`] transform.
+    pub fn model_to_screen(&self, point: Vec2, rect: egui::Rect) -> egui::Pos2 {
+        project_to_screen(self.scene_view(rect.size()), point, rect)
+    }
+
+    /// Draws every active bone of the current pose, as joint circles and
+    /// segments towards their tips, when the skeleton is painted into `rect`.
+    ///
+    /// Bones without length are only drawn as joints.
+    pub fn draw_bones(&self, painter: &egui::Painter, rect: egui::Rect) {
+        self.paint_bones(painter, rect, self.scene_view(rect.size()));
+    }
+
+    fn paint_bones(&self, painter: &egui::Painter, rect: egui::Rect, scene_view: Mat4) {
+        let color = egui::Color32::from_rgb(255, 80, 80);
+        let mut shapes = vec![];
+        for bone in self.controller.skeleton.bones() {
+            if !bone.active() {
+                continue;
+            }
+            let origin = Vec2::new(bone.world_x(), bone.world_y());
+            let screen_origin = project_to_screen(scene_view, origin, rect);
+            let length = bone.data().length();
+            if length > 0.0 {
+                let tip = origin + Vec2::new(bone.a(), bone.c()) * length;
+                let screen_tip = project_to_screen(scene_view, tip, rect);
+                shapes.push(egui::Shape::line_segment(
+                    [screen_origin, screen_tip],
+                    egui::Stroke::new(2.0, color),
+                ));
+            }
+            shapes.push(egui::Shape::circle_filled(screen_origin, 3.0, color));
+        }
+        painter.extend(shapes);
+    }
+
+    /// Draws the overlays enabled in [`SpineOptions::debug`] on top of the
+    /// skeleton painted into `rect`.
+    #[cfg(any(feature = "wgpu", feature = "glow"))]
+    fn paint_debug(&self, painter: &egui::Painter, rect: egui::Rect, scene_view: Mat4) {
+        let debug = self.options.debug;
+        let to_screen = |point| project_to_screen(scene_view, point, rect);
+
+        if debug.contains(DebugDraw::Triangles) {
+            let stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(0, 200, 255));
+            let mut shapes = vec![];
+            for_each_visible_attachment(&self.controller.skeleton, |_, world_vertices, indices| {
+                let vertex = |index: u16| {
+                    let index = usize::from(index) * 2;
+                    to_screen(Vec2::new(world_vertices[index], world_vertices[index + 1]))
+                };
+                for triangle in indices.chunks_exact(3) {
+                    let points = triangle.iter().map(|&index| vertex(index)).collect();
+                    shapes.push(egui::Shape::closed_line(points, stroke));
+                }
+            });
+            painter.extend(shapes);
+        }
+
+        if debug.contains(DebugDraw::Bones) {
+            self.paint_bones(painter, rect, scene_view);
+        }
+
+        if debug.contains(DebugDraw::Bounds)
+            && let Some(bounds) = skeleton_bounds(&self.controller.skeleton)
+        {
+            let min = to_screen(Vec2::new(bounds.min.x, bounds.min.y));
+            let max = to_screen(Vec2::new(bounds.max.x, bounds.max.y));
+            painter.rect_stroke(
+                egui::Rect::from_two_pos(min, max),
+                0.0,
+                egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 200, 0)),
+                egui::StrokeKind::Middle,
+            );
+        }
+    }
+
+    fn track_mut(
+        &mut self,
+        track: usize,
+    ) -> Result<CTmpMut<'_, AnimationState, TrackEntry>, SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        controller
+            .animation_state
+            .track_at_index_mut(track)
+            .ok_or_else(|| SpineError::NotFound {
+                what: "Track".to_owned(),
+                name: track.to_string(),
+            })
+    }
+
+    fn slot_mut(&mut self, slot: &str) -> Result<CTmpMut<'_, Skeleton, Slot>, SpineError> {
+        let controller = unique_controller(&mut self.controller)?;
+        controller
+            .skeleton
+            .find_slot_mut(slot)
+            .ok_or_else(|| SpineError::NotFound {
+                what: "Slot".to_owned(),
+                name: slot.to_owned(),
+            })
+    }
+
+    fn dispatch_events(&mut self) {
+        let events = self.events.take();
+        if self.options.queue_events {
+            self.queued_events.extend(events.iter().cloned());
+        }
+        if let Some(event_cb) = &self.options.event_cb {
+            events.into_iter().for_each(|event| event_cb.call(event));
+        }
+    }
+}
+
+#[cfg(any(feature = "wgpu", feature = "glow"))]
+impl Widget for &mut Spine {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let dt = match self.options.time_step {
+            TimeStep::Realtime => {
+                let dt = ui.input(|i| i.stable_dt).max(self.options.min_dt);
+                Some(self.options.max_dt.map_or(dt, |max_dt| dt.min(max_dt)))
+            }
+            TimeStep::Fixed(dt) => Some(dt),
+            TimeStep::Manual => None,
+        };
+        let updated = dt.is_none_or(|dt| self.update(dt).is_ok());
+        let Some(controller) = Arc::get_mut(&mut self.controller).filter(|_| updated) else {
+            panic!(
+                "Tried to render the same Spine model multiple times in the same render pass; \
+                 use `Spine::clone_instance` to draw it more than once"
+            );
+        };
+        let mut renderables = controller.combined_renderables();
+        apply_tint(&mut renderables, self.options.tint);
+        self.draw_stats = DrawStats::new(&renderables);
+
+        if self.options.continuous_repaint || self.is_animating() || renderer::is_loading_textures()
+        {
+            ui.ctx().request_repaint();
+        }
+        if let Some(retry) = renderer::take_texture_retry() {
+            ui.ctx().request_repaint_after(retry);
+        }
+
+        let controller = Arc::clone(&self.controller);
+        let meshes = Meshes::new(controller, renderables);
+
+        let rect = ui.available_rect_before_wrap();
+        let response = ui.allocate_rect(rect, egui::Sense::click_and_drag());
+        if self.options.scene.interactive {
+            self.options.scene.interact(ui, &response);
+        }
+        let scene_view = self.scene_view(rect.size());
+        let cull_mode = self.cull_mode();
+        let outline = self
+            .options
+            .outline
+            .and_then(|outline| outline.pass(rect.size(), scene_view, self.options.color_space));
+
+        if let Some(background) = self.options.scene.background {
+            ui.painter().rect_filled(rect, 0.0, background);
+        }
+        if let Some(checkerboard) = &self.options.scene.checkerboard {
+            checkerboard.paint(ui.painter(), rect);
+        }
+        ui.painter().add(renderer::paint_callback(
+            rect,
+            RendererCallback::new(
+                meshes,
+                scene_view,
+                cull_mode,
+                self.options.desaturate,
+                self.options.scene.depth,
+                outline,
+            ),
+        ));
+        if !self.options.debug.is_empty() {
+            self.paint_debug(ui.painter(), rect, scene_view);
+        }
+
+        response
+    }
+}
+
+/// Multiplies the vertex colors of `renderables` with `tint`.
+fn apply_tint(renderables: &mut [SkeletonCombinedRenderable], tint: egui::Color32) {
+    if tint == egui::Color32::WHITE {
+        return;
+    }
+
+    let [r, g, b, a] = tint.to_srgba_unmultiplied().map(|c| f32::from(c) / 255.);
+    for renderable in renderables {
+        // NOTE: Premultiplied colors need to be faded along with the alpha, and
+        // so does the dark color, which would otherwise still be added.
+        let (tint, dark_tint) = match renderable.premultiplied_alpha {
+            true => ([r * a, g * a, b * a, a], a),
+            false => ([r, g, b, a], 1.),
+        };
+        for color in &mut renderable.colors {
+            for (channel, tint) in color.iter_mut().zip(tint) {
+                *channel *= tint;
+            }
+        }
+        for dark_color in &mut renderable.dark_colors {
+            for channel in &mut dark_color[..3] {
+                *channel *= dark_tint;
+            }
+        }
+    }
+}
+
+/// Returns the controller if no render is currently holding onto it.
+fn unique_controller(
+    controller: &mut Arc<SkeletonController>,
+) -> Result<&mut SkeletonController, SpineError> {
+    Arc::get_mut(controller).ok_or(SpineError::Busy)
+}
+
+/// Calls `f` with the slot, world vertices (as `x, y` pairs) and triangle
+/// indices of every attachment visible in the current pose of `skeleton`, in
+/// draw order.
+///
+/// NOTE: Attachments are clipped by the clipping attachments before them, just
+/// like the meshes drawn by `SkeletonController::combined_renderables`.
+fn for_each_visible_attachment(skeleton: &Skeleton, mut f: impl FnMut(&Slot, &[f32], &[u16])) {
+    const REGION_INDICES: [u16; 6] = [0, 1, 2, 2, 3, 0];
+    let mut world_vertices = vec![];
+    let mut clipper = SkeletonClipping::new();
+
+    for slot in skeleton.draw_order() {
+        let attachment = slot.attachment().filter(|_| slot.bone().active());
+        if let Some(clipping) = attachment.as_ref().and_then(|a| a.as_clipping()) {
+            clipper.clip_start(&slot, &clipping);
+            continue;
+        }
+
+        if let Some(mesh) = attachment.as_ref().and_then(|a| a.as_mesh()) {
+            let length = mesh.world_vertices_length();
+            world_vertices.resize(length as usize, 0.0);
+            // SAFETY: The attachment was taken from this same slot, and the
+            // triangles array holds `triangles_count` indices.
+            let indices = unsafe {
+                mesh.compute_world_vertices(&slot, 0, length, &mut world_vertices, 0, 2);
+                match mesh.triangles_count() {
+                    0 => &[],
+                    count => std::slice::from_raw_parts(mesh.triangles(), count as usize),
+                }
+            };
+            clip_attachment(&clipper, &slot, &world_vertices, indices, &mut f);
+        } else if let Some(region) = attachment.as_ref().and_then(|a| a.as_region()) {
+            world_vertices.resize(8, 0.0);
+            // SAFETY: The attachment was taken from this same slot.
+            unsafe { region.compute_world_vertices(&slot, &mut world_vertices, 0, 2) };
+            clip_attachment(&clipper, &slot, &world_vertices, &REGION_INDICES, &mut f);
+        }
+        clipper.clip_end(&slot);
+    }
+    clipper.clip_end2();
+}
+
+/// Calls `f` with the triangles of an attachment, clipped by `clipper`.
+fn clip_attachment(
+    clipper: &SkeletonClipping,
+    slot: &Slot,
+    world_vertices: &[f32],
+    indices: &[u16],
+    f: &mut impl FnMut(&Slot, &[f32], &[u16]),
+) {
+    if !clipper.is_clipping() {
+        f(slot, world_vertices, indices);
+        return;
+    }
+
+    let mut vertices: Vec<[f32; 2]> = world_vertices
+        .chunks_exact(2)
+        .map(|vertex| [vertex[0], vertex[1]])
+        .collect();
+    // NOTE: The UVs are clipped along with the vertices, but aren't needed.
+    let mut uvs = vertices.clone();
+    let mut indices = indices.to_vec();
+    // SAFETY: The indices come from the attachment of the vertices, and the
+    // clipped arrays are owned by the clipper, which isn't modified until the
+    // next attachment is clipped.
+    unsafe {
+        clipper.clip_triangles(&mut vertices, &mut indices, &mut uvs, 2);
+        let clipper = &*clipper.c_ptr();
+        let vertices = &*clipper.clippedVertices;
+        let triangles = &*clipper.clippedTriangles;
+        let vertices = match vertices.size {
+            0 => &[],
+            size => std::slice::from_raw_parts(vertices.items, size as usize),
+        };
+        let triangles = match triangles.size {
+            0 => &[],
+            size => std::slice::from_raw_parts(triangles.items, size as usize),
+        };
+        f(slot, vertices, triangles);
+    }
+}
+
+/// Axis-aligned bounds, in model space, of the attachments visible in the
+/// current pose of `skeleton`.
+fn skeleton_bounds(skeleton: &Skeleton) -> Option<egui::Rect> {
+    let mut bounds = egui::Rect::NOTHING;
+    for_each_visible_attachment(skeleton, |_, world_vertices, _| {
+        for vertex in world_vertices.chunks_exact(2) {
+            bounds.extend_with(egui::pos2(vertex[0], vertex[1]));
+        }
+    });
+
+    bounds.is_finite().then_some(bounds)
+}
+
+/// Projects `point`, in model space, into `rect` with `scene_view`.
+fn project_to_screen(scene_view: Mat4, point: Vec2, rect: egui::Rect) -> egui::Pos2 {
+    let ndc = scene_view.project_point3(point.extend(0.));
+    rect.center() + egui::vec2(ndc.x, -ndc.y) * (rect.size() * 0.5)
+}
+
+/// Whether `point` is inside of (or on an edge of) the triangle.
+///
+/// NOTE: Degenerate triangles, e.g. from a collapsed mesh, contain nothing.
+fn triangle_contains(point: Vec2, [a, b, c]: [Vec2; 3]) -> bool {
+    if (b - a).perp_dot(c - a).abs() <= f32::EPSILON {
+        return false;
+    }
+
+    let d1 = (point - b).perp_dot(a - b);
+    let d2 = (point - c).perp_dot(b - c);
+    let d3 = (point - a).perp_dot(c - a);
+
+    let has_negative = d1 < 0. || d2 < 0. || d3 < 0.;
+    let has_positive = d1 > 0. || d2 > 0. || d3 > 0.;
+    !(has_negative && has_positive)
+}
+
+#[derive(Clone, Debug)]
+pub enum SkeletonKind<P>
+where
+    P: AsRef<Path>,
+{
+    Json(P),
+    Binary(P),
+    /// Either of them, told apart by the extension of the file (`.json` or
+    /// `.skel`), or else by its first bytes.
+    Auto(P),
+}
+
+impl<P> SkeletonKind<P>
+where
+    P: AsRef<Path>,
+{
+    fn to_path_buf(&self) -> SkeletonKind<PathBuf> {
+        match self {
+            Self::Json(path) => SkeletonKind::Json(path.as_ref().to_owned()),
+            Self::Binary(path) => SkeletonKind::Binary(path.as_ref().to_owned()),
+            Self::Auto(path) => SkeletonKind::Auto(path.as_ref().to_owned()),
+        }
+    }
+
+    #[inline]
+    fn path(&self) -> &Path {
+        match self {
+            Self::Json(path) | Self::Binary(path) | Self::Auto(path) => path.as_ref(),
+        }
+    }
+
+    #[inline]
+    fn as_ref(&self) -> SkeletonKind<&Path> {
+        match self {
+            Self::Json(path) => SkeletonKind::Json(path.as_ref()),
+            Self::Binary(path) => SkeletonKind::Binary(path.as_ref()),
+            Self::Auto(path) => SkeletonKind::Auto(path.as_ref()),
+        }
+    }
+
+    /// Tells whether an [`SkeletonKind::Auto`] file is JSON or binary.
+    fn detect(self) -> Result<Self, LoadError> {
+        let SkeletonKind::Auto(path) = self else {
+            return Ok(self);
+        };
+
+        let extension = path.as_ref().extension().and_then(|e| e.to_str());
+        match extension.map(str::to_ascii_lowercase).as_deref() {
+            Some("json") => return Ok(SkeletonKind::Json(path)),
+            Some("skel") => return Ok(SkeletonKind::Binary(path)),
+            _ => {}
+        }
+
+        let mut header = [0; 32];
+        let header = std::fs::File::open(&path)
+            .and_then(|mut file| {
+                let len = std::io::Read::read(&mut file, &mut header)?;
+                Ok(&header[..len])
+            })
+            .unwrap_or_default();
+        // NOTE: Binary skeletons start with an 8 bytes hash, followed by the
+        // editor version, prefixed with its length plus one.
+        let is_binary = |header: &[u8]| {
+            let Some((&len, rest)) = header.get(8..).and_then(<[u8]>::split_first) else {
+                return false;
+            };
+            let version = rest
+                .get(..usize::from(len).saturating_sub(1))
+                .unwrap_or(&[]);
+            version.first().is_some_and(u8::is_ascii_digit) && version.contains(&b'.')
+        };
+        match header.iter().find(|byte| !byte.is_ascii_whitespace()) {
+            Some(b'{') => Ok(SkeletonKind::Json(path)),
+            Some(_) if is_binary(header) => Ok(SkeletonKind::Binary(path)),
+            _ => Err(LoadError::UnknownFormat {
+                path: path.as_ref().to_owned(),
+            }),
+        }
+    }
+
+    #[inline]
+    fn read(self, atlas: Arc<Atlas>) -> Result<SkeletonData, LoadError> {
+        let (skeleton_data, path) = match self.detect()? {
+            SkeletonKind::Json(path) => {
+                let skeleton_data = SkeletonJson::new(atlas)
+                    .read_skeleton_data_file(&path)
+                    .map_err(|source| LoadError::Json {
+                        path: path.as_ref().to_owned(),
+                        source,
+                    })?;
+                (skeleton_data, path)
+            }
+            SkeletonKind::Binary(path) => {
+                let skeleton_data = SkeletonBinary::new(atlas)
+                    .read_skeleton_data_file(&path)
+                    .map_err(|source| LoadError::Binary {
+                        path: path.as_ref().to_owned(),
+                        source,
+                    })?;
+                (skeleton_data, path)
+            }
+            SkeletonKind::Auto(_) => unreachable!("the format was detected above"),
+        };
+
+        // NOTE: Skeletons exported without a version are trusted to match.
+        if let Some(version) = skeleton_data.version()
+            && !version.is_empty()
+            && !LoadError::is_supported_version(version)
+        {
+            return Err(LoadError::UnsupportedVersion {
+                path: path.as_ref().to_owned(),
+                version: version.to_owned(),
+            });
+        }
+        Ok(skeleton_data)
+    }
+}
+
+/// How a [`Spine`] is loaded, updated and drawn.
+///
+/// NOTE: The options can be cloned to load several models configured alike;
+/// clones share the same [`SpineOptions::event_cb`] closure.
+#[derive(Clone, Debug)]
+pub struct SpineOptions {
+    pub scene: Scene,
+    pub animation: Animation,
+    /// Crossfade duration, in seconds, between animations without an explicit
+    /// mix set with [`Spine::set_mix`].
+    pub default_mix: f32,
+    /// Request a repaint every frame, even if nothing is being animated.
+    pub continuous_repaint: bool,
+    /// How much time the widget advances the animations every frame.
+    pub time_step: TimeStep,
+    /// Minimum seconds the animations are advanced every frame with
+    /// [`TimeStep::Realtime`].
+    pub min_dt: f32,
+    /// Maximum seconds the animations are advanced every frame with
+    /// [`TimeStep::Realtime`], so that they slow down instead of jumping
+    /// forward (and physics don't explode) after the app stalls.
+    ///
+    /// [`SpineOptions::RECOMMENDED_MAX_DT`] is a good cap for most apps.
+    pub max_dt: Option<f32>,
+    /// How physics constraints are applied on every update.
+    pub physics: Physics,
+    /// Overlays drawn on top of the skeleton, to debug its meshes.
+    pub debug: DebugDraw,
+    /// Multiplied with the color of every vertex, e.g. to fade the whole
+    /// skeleton out without changing the color of its slots.
+    pub tint: egui::Color32,
+    /// How much the skeleton is drawn in grayscale, from `0.0` (full color) to
+    /// `1.0` (no color at all), e.g. for stunned or paused characters.
+    pub desaturate: f32,
+    /// Outline drawn around the skeleton, e.g. to highlight it while selected
+    /// or hovered.
+    pub outline: Option<Outline>,
+    /// Called with every event emitted while the animation state is updated,
+    /// that passes the filter of the callback; see [`Spine::on_event`].
+    pub event_cb: Option<EventCallback>,
+    /// Keep every event emitted while the animation state is updated, until
+    /// they are taken with [`Spine::drain_events`].
+    ///
+    /// NOTE: Events are kept in addition to calling `event_cb`, and pile up
+    /// until they are drained.
+    pub queue_events: bool,
+    /// Color space of the vertex colors.
+    pub color_space: ColorSpace,
+    /// Winding order of the triangles of the exported skeleton.
+    pub cull_direction: CullDirection,
+    /// Whether the atlas textures have premultiplied alpha.
+    ///
+    /// When `None`, it is detected from the atlas pages.
+    pub premultiplied_alpha: Option<bool>,
+}
+
+impl Default for SpineOptions {
+    fn default() -> Self {
+        Self {
+            scene: Scene::default(),
+            animation: Animation::default(),
+            default_mix: 0.0,
+            continuous_repaint: false,
+            time_step: TimeStep::Realtime,
+            min_dt: 0.001,
+            max_dt: None,
+            physics: Physics::Update,
+            debug: DebugDraw::empty(),
+            tint: egui::Color32::WHITE,
+            desaturate: 0.0,
+            outline: None,
+            event_cb: None,
+            queue_events: false,
+            color_space: ColorSpace::SRGB,
+            cull_direction: CullDirection::CounterClockwise,
+            premultiplied_alpha: None,
+        }
+    }
+}
+
+impl SpineOptions {
+    /// A [`SpineOptions::max_dt`] of `0.1` seconds, as recommended by
+    /// `egui::InputState::stable_dt`, so the animations only slow down when the
+    /// frame rate drops below 10.
+    pub const RECOMMENDED_MAX_DT: f32 = 0.1;
+
+    /// Events that need to be collected while updating, for `event_cb` or
+    /// `queue_events`.
+    fn listened_events(&self) -> EventFilter {
+        match (&self.event_cb, self.queue_events) {
+            (_, true) => EventFilter::default(),
+            (Some(event_cb), false) => event_cb.filter(),
+            (None, false) => EventFilter::NONE,
+        }
+    }
+
+    /// Fluent alternative to the nested struct literals, e.g.
+    /// `SpineOptions::builder().scale(0.7).animation_index(2).build()`.
+    pub fn builder() -> SpineOptionsBuilder {
+        SpineOptionsBuilder::default()
+    }
+}
+
+/// Builder of [`SpineOptions`]; see [`SpineOptions::builder`].
+///
+/// Fields that are not set keep their default value.
+#[derive(Clone, Debug, Default)]
+#[must_use]
+pub struct SpineOptionsBuilder {
+    options: SpineOptions,
+}
+
+impl SpineOptionsBuilder {
+    pub fn build(self) -> SpineOptions {
+        self.options
+    }
+
+    pub fn scene(mut self, scene: Scene) -> Self {
+        self.options.scene = scene;
+        self
+    }
+
+    /// See [`Scene::position`].
+    pub fn position(mut self, position: Vec2) -> Self {
+        self.options.scene.position = position;
+        self
+    }
+
+    /// See [`Scene::angle`].
+    pub fn angle(mut self, angle: f32) -> Self {
+        self.options.scene.angle = angle;
+        self
+    }
+
+    /// See [`Scene::scale`].
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.options.scene.scale = scale;
+        self
+    }
+
+    /// See [`Scene::reflect`].
+    pub fn reflect(mut self, reflect: Reflect) -> Self {
+        self.options.scene.reflect = reflect;
+        self
+    }
+
+    /// See [`Scene::flip_x`].
+    pub fn flip_x(mut self, flip_x: bool) -> Self {
+        self.options.scene.flip_x = flip_x;
+        self
+    }
+
+    /// See [`Scene::flip_y`].
+    pub fn flip_y(mut self, flip_y: bool) -> Self {
+        self.options.scene.flip_y = flip_y;
+        self
+    }
+
+    /// See [`Scene::fit`].
+    pub fn fit(mut self, fit: Fit) -> Self {
+        self.options.scene.fit = Some(fit);
+        self
+    }
+
+    /// See [`Scene::padding`].
+    pub fn padding(mut self, padding: f32) -> Self {
+        self.options.scene.padding = padding;
+        self
+    }
+
+    pub fn animation(mut self, animation: Animation) -> Self {
+        self.options.animation = animation;
+        self
+    }
+
+    /// Sets the initial animation by index; see [`Animation::id`].
+    pub fn animation_index(mut self, index: usize) -> Self {
+        self.options.animation.id = Some(AnimationId::Index(index));
+        self
+    }
+
+    /// Sets the initial animation by name; see [`Animation::id`].
+    pub fn animation_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        self.options.animation.id = Some(AnimationId::Name(name.into()));
+        self
+    }
+
+    /// Starts in the setup pose; see [`Animation::id`].
+    pub fn no_animation(mut self) -> Self {
+        self.options.animation.id = None;
+        self
+    }
+
+    /// See [`Animation::cull_mode`].
+    pub fn cull_mode(mut self, cull_mode: Face) -> Self {
+        self.options.animation.cull_mode = Some(cull_mode);
+        self
+    }
+
+    /// See [`Animation::playback_speed`].
+    pub fn playback_speed(mut self, playback_speed: f32) -> Self {
+        self.options.animation.playback_speed = playback_speed;
+        self
+    }
+
+    /// See [`Animation::loop_`].
+    pub fn loop_(mut self, loop_: bool) -> Self {
+        self.options.animation.loop_ = loop_;
+        self
+    }
+
+    /// See [`Animation::skin`].
+    pub fn skin(mut self, skin: impl Into<Cow<'static, str>>) -> Self {
+        self.options.animation.skin = Some(skin.into());
+        self
+    }
+
+    /// See [`SpineOptions::default_mix`].
+    pub fn default_mix(mut self, default_mix: f32) -> Self {
+        self.options.default_mix = default_mix;
+        self
     }
 
-    fn __new(
-        atlas: &Path,
-        skel: SkeletonKind<&Path>,
-        options: SpineOptions,
-    ) -> Result<Self, SpineError> {
-        let atlas = Arc::new(Atlas::new_from_file(atlas)?);
-        let premultiplied_alpha = atlas.pages().any(|page| page.pma());
-        let skel = Arc::new(skel.read(atlas)?);
+    /// See [`SpineOptions::continuous_repaint`].
+    pub fn continuous_repaint(mut self, continuous_repaint: bool) -> Self {
+        self.options.continuous_repaint = continuous_repaint;
+        self
+    }
 
-        // TODO(Unavailable): Set any crossfades.
-        let animation_state = Arc::new(AnimationStateData::new(skel.clone()));
-        let controller = SkeletonController::new(skel.clone(), animation_state);
-        let settings = SkeletonControllerSettings {
-            color_space: ColorSpace::SRGB,
-            cull_direction: CullDirection::CounterClockwise,
-            premultiplied_alpha,
-        };
-        let mut controller = controller.with_settings(settings);
+    /// See [`SpineOptions::time_step`].
+    pub fn time_step(mut self, time_step: TimeStep) -> Self {
+        self.options.time_step = time_step;
+        self
+    }
 
-        // TODO(Unavailable): Allow users to inspect animation events.
-
-        // TODO(Unavailable): Configuration
-        let should_loop = true;
-        let animation_state = &mut controller.animation_state;
-        match &options.animation.id {
-            AnimationId::Index(index) => {
-                match controller.skeleton.data().animations().nth(*index) {
-                    Some(animation) => animation_state.set_animation(0, &animation, should_loop),
-                    None => {
-                        return Err(SpineError::NotFound {
-                            what: "Animation".to_owned(),
-                            name: index.to_string(),
-                        });
-                    }
-                }
-            }
-            AnimationId::Name(name) => {
-                animation_state.set_animation_by_name(0, &name, should_loop)?
-            }
-        };
+    /// See [`SpineOptions::min_dt`].
+    pub fn min_dt(mut self, min_dt: f32) -> Self {
+        self.options.min_dt = min_dt;
+        self
+    }
 
-        // TODO(Unvailable): `Skin` handling
+    /// See [`SpineOptions::max_dt`].
+    pub fn max_dt(mut self, max_dt: f32) -> Self {
+        self.options.max_dt = Some(max_dt);
+        self
+    }
 
-        Ok(Self {
-            options,
-            controller: Arc::new(controller),
-        })
+    /// Advances the animations by the whole time elapsed, however long; see
+    /// [`SpineOptions::max_dt`].
+    pub fn no_max_dt(mut self) -> Self {
+        self.options.max_dt = None;
+        self
     }
-}
 
-impl Spine {
-    pub fn options(&self) -> &SpineOptions {
-        &self.options
+    /// See [`SpineOptions::physics`].
+    pub fn physics(mut self, physics: Physics) -> Self {
+        self.options.physics = physics;
+        self
     }
 
-    // TODO(Unavailable): Iterator that returns all the available animations.
+    /// See [`SpineOptions::debug`].
+    pub fn debug(mut self, debug: DebugDraw) -> Self {
+        self.options.debug = debug;
+        self
+    }
 
-    // TODO(Unavailable): Individual `set_animation_*` methods.
+    /// See [`SpineOptions::tint`].
+    pub fn tint(mut self, tint: egui::Color32) -> Self {
+        self.options.tint = tint;
+        self
+    }
 
-    pub fn scene_mut(&mut self) -> &mut Scene {
-        &mut self.options.scene
+    /// See [`SpineOptions::desaturate`].
+    pub fn desaturate(mut self, desaturate: f32) -> Self {
+        self.options.desaturate = desaturate;
+        self
     }
-}
 
-impl Widget for &mut Spine {
-    fn ui(self, ui: &mut Ui) -> Response {
-        ui.ctx().request_repaint();
+    /// See [`SpineOptions::outline`].
+    pub fn outline(mut self, color: egui::Color32, width: f32) -> Self {
+        self.options.outline = Some(Outline { color, width });
+        self
+    }
 
-        let Some(controller) = Arc::get_mut(&mut self.controller) else {
-            panic!("Tried to render the same Spine model multiple times in the same render pass");
-        };
+    /// See [`SpineOptions::event_cb`].
+    pub fn event_cb<F>(mut self, callback: F) -> Self
+    where
+        F: FnMut(SpineEvent) + Send + 'static,
+    {
+        self.options.event_cb = Some(EventCallback::new(callback));
+        self
+    }
 
-        let dt = ui.input(|i| i.stable_dt).max(0.001);
-        controller.update(dt, Physics::Update);
+    /// See [`SpineOptions::event_cb`] and [`Spine::on_event`].
+    pub fn on_event<F>(mut self, track: Option<usize>, kinds: EventKinds, callback: F) -> Self
+    where
+        F: FnMut(SpineEvent) + Send + 'static,
+    {
+        let callback = EventCallback::new(callback).with_filter(EventFilter { track, kinds });
+        self.options.event_cb = Some(callback);
+        self
+    }
 
-        let renderables = controller.combined_renderables();
-        let controller = Arc::clone(&self.controller);
-        let meshes = Meshes::new(controller, renderables);
+    /// See [`SpineOptions::queue_events`].
+    pub fn queue_events(mut self, queue_events: bool) -> Self {
+        self.options.queue_events = queue_events;
+        self
+    }
 
-        let rect = ui.available_rect_before_wrap();
-        let scene_view = self.options.scene.create_scene_view(rect.size());
-        let cull_mode = self.options.animation.cull_mode;
+    /// See [`SpineOptions::color_space`].
+    pub fn color_space(mut self, color_space: ColorSpace) -> Self {
+        self.options.color_space = color_space;
+        self
+    }
 
-        ui.painter().add(egui_wgpu::Callback::new_paint_callback(
-            rect,
-            RendererCallback {
-                meshes,
-                scene_view,
-                cull_mode,
-            },
-        ));
+    /// See [`SpineOptions::cull_direction`].
+    pub fn cull_direction(mut self, cull_direction: CullDirection) -> Self {
+        self.options.cull_direction = cull_direction;
+        self
+    }
 
-        ui.response()
+    /// See [`SpineOptions::premultiplied_alpha`].
+    pub fn premultiplied_alpha(mut self, premultiplied_alpha: bool) -> Self {
+        self.options.premultiplied_alpha = Some(premultiplied_alpha);
+        self
     }
 }
 
-pub enum SkeletonKind<P>
-where
-    P: AsRef<Path>,
-{
-    Json(P),
-    Binary(P),
+/// How much time the widget advances the animations every frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TimeStep {
+    /// The time elapsed since the previous frame.
+    #[default]
+    Realtime,
+    /// The same amount of seconds every frame, regardless of the frame rate;
+    /// for reproducible renders.
+    Fixed(f32),
+    /// Nothing; the widget only draws the current pose, and the animations are
+    /// advanced by calling [`Spine::update`], e.g. on every tick of a game
+    /// loop running at its own rate.
+    ///
+    /// The widget doesn't request repaints while animating either, so a
+    /// repaint should be requested after updating (unless
+    /// [`SpineOptions::continuous_repaint`] is set).
+    Manual,
 }
 
-impl<P> SkeletonKind<P>
-where
-    P: AsRef<Path>,
-{
-    #[inline]
-    fn as_ref(&self) -> SkeletonKind<&Path> {
-        match self {
-            Self::Json(path) => SkeletonKind::Json(path.as_ref()),
-            Self::Binary(path) => SkeletonKind::Binary(path.as_ref()),
-        }
-    }
+/// How physics constraints (e.g. hair or cloth) are applied on every update.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Physics {
+    /// Physics are neither updated nor applied; for a stable preview.
+    None,
+    /// Physics are reset to the current pose on every update; see
+    /// [`Spine::reset_physics`] to reset them once.
+    Reset,
+    /// Physics are updated, and applied to the pose.
+    #[default]
+    Update,
+    /// Physics are not updated, but their last pose is still applied.
+    Pose,
+}
 
-    #[inline]
-    fn read(self, atlas: Arc<Atlas>) -> Result<SkeletonData, SpineError> {
+impl Physics {
+    fn into_spine(self) -> rusty_spine::Physics {
         match self {
-            SkeletonKind::Json(path) => SkeletonJson::new(atlas).read_skeleton_data_file(path),
-            SkeletonKind::Binary(path) => SkeletonBinary::new(atlas).read_skeleton_data_file(path),
+            Physics::None => rusty_spine::Physics::None,
+            Physics::Reset => rusty_spine::Physics::Reset,
+            Physics::Update => rusty_spine::Physics::Update,
+            Physics::Pose => rusty_spine::Physics::Pose,
         }
     }
 }
 
-#[derive(Clone, Debug, Default)]
-pub struct SpineOptions {
-    pub scene: Scene,
-    pub animation: Animation,
-    // TODO(Unavailable): event_cb: Box<dyn Fn()>
-}
-
 #[derive(Clone, Debug)]
 pub struct Scene {
     pub position: Vec2,
     pub angle: f32,
     pub scale: f32,
+    /// Reflects the projection; see [`Reflect`].
     pub reflect: Reflect,
+    /// Mirrors the skeleton horizontally, by negating its scale.
+    ///
+    /// Unlike [`Reflect`], bounds, hit tests and bone positions are mirrored
+    /// too, and [`Animation::cull_mode`] keeps culling the same triangles.
+    pub flip_x: bool,
+    /// Mirrors the skeleton vertically; see [`Scene::flip_x`].
+    pub flip_y: bool,
+    /// Scales the skeleton to the rect it is drawn into, and centers it.
+    ///
+    /// The bounds are computed from the current pose, so the fitted scale
+    /// follows the animation. `position` and `scale` are still applied on top
+    /// of the fitted transform.
+    pub fit: Option<Fit>,
+    /// Space, in points, left empty on each side of the rect when fitting.
+    pub padding: f32,
+    /// Fills the rect with this color before drawing the skeleton.
+    pub background: Option<egui::Color32>,
+    /// Draws a checkerboard behind the skeleton, on top of
+    /// [`Scene::background`].
+    pub checkerboard: Option<Checkerboard>,
+    /// Pans the scene by dragging the widget, and zooms it towards the cursor
+    /// by scrolling or pinching over it.
+    ///
+    /// The zoom keeps [`Scene::scale`] within [`Scene::INTERACTIVE_SCALE`].
+    pub interactive: bool,
+    /// Depth of the skeleton, from `0.0` (near) to `1.0` (far), when it is
+    /// depth tested against other content; see
+    /// `WgpuContextOptions::depth_format`.
+    ///
+    /// NOTE: It is only applied when drawing, so it doesn't change
+    /// [`Scene::view_matrix`]; and it is ignored by the `glow` renderer, which
+    /// doesn't depth test.
+    pub depth: f32,
 }
 
 impl Scene {
-    pub(crate) fn create_scene_view(&self, size: egui::Vec2) -> Mat4 {
+    /// Range of [`Scene::scale`] reachable by zooming an
+    /// [`Scene::interactive`] scene.
+    pub const INTERACTIVE_SCALE: std::ops::RangeInclusive<f32> = 0.05..=20.0;
+
+    /// Returns the scene with its [`Scene::position`] set to `position`.
+    #[must_use]
+    pub fn at(mut self, position: Vec2) -> Self {
+        self.position = position;
+        self
+    }
+
+    /// Returns the scene with its [`Scene::angle`] set to `angle`.
+    #[must_use]
+    pub fn rotated(mut self, angle: f32) -> Self {
+        self.angle = angle;
+        self
+    }
+
+    /// Returns the scene with its [`Scene::scale`] set to `scale`.
+    #[must_use]
+    pub fn scaled(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Returns the scene with its [`Scene::reflect`] set to `reflect`.
+    #[must_use]
+    pub fn reflected(mut self, reflect: Reflect) -> Self {
+        self.reflect = reflect;
+        self
+    }
+
+    /// Chainable alternative to assigning [`Scene::position`], e.g. through
+    /// [`Spine::scene_mut`].
+    pub fn set_position(&mut self, position: Vec2) -> &mut Self {
+        self.position = position;
+        self
+    }
+
+    /// See [`Scene::set_position`].
+    pub fn set_angle(&mut self, angle: f32) -> &mut Self {
+        self.angle = angle;
+        self
+    }
+
+    /// See [`Scene::set_position`].
+    pub fn set_scale(&mut self, scale: f32) -> &mut Self {
+        self.scale = scale;
+        self
+    }
+
+    /// See [`Scene::set_position`].
+    pub fn set_reflect(&mut self, reflect: Reflect) -> &mut Self {
+        self.reflect = reflect;
+        self
+    }
+
+    /// Returns the scene with a [`Checkerboard`] of `size` points squares,
+    /// alternating between `color_a` and `color_b`, behind the skeleton.
+    #[must_use]
+    pub fn checkerboard(
+        mut self,
+        size: f32,
+        color_a: egui::Color32,
+        color_b: egui::Color32,
+    ) -> Self {
+        self.checkerboard = Some(Checkerboard {
+            size,
+            color_a,
+            color_b,
+        });
+        self
+    }
+
+    /// Pans and zooms the scene from the input on `response`; see
+    /// [`Scene::interactive`].
+    #[cfg(any(feature = "wgpu", feature = "glow"))]
+    fn interact(&mut self, ui: &Ui, response: &Response) {
+        // NOTE: The scene is centered on the rect with `y` pointing up, and
+        // reflecting it also reflects the input.
+        let axes = egui::vec2(
+            match self.reflect.contains(Reflect::YAxis) {
+                true => -1.,
+                false => 1.,
+            },
+            match self.reflect.contains(Reflect::XAxis) {
+                true => 1.,
+                false => -1.,
+            },
+        );
+        let to_scene = |delta: egui::Vec2| Vec2::new(delta.x * axes.x, delta.y * axes.y);
+
+        if response.dragged() {
+            self.position += to_scene(response.drag_delta());
+        }
+
+        if let Some(hover_pos) = response.hover_pos() {
+            let (scroll, zoom) = ui.input(|i| (i.smooth_scroll_delta.y, i.zoom_delta()));
+            let factor = (scroll * 0.002).exp() * zoom;
+            if factor != 1. && self.scale != 0. {
+                let (min, max) = Self::INTERACTIVE_SCALE.into_inner();
+                let scale = (self.scale * factor).clamp(min, max);
+                let cursor = to_scene(hover_pos - response.rect.center());
+                self.position = cursor + (self.position - cursor) * (scale / self.scale);
+                self.scale = scale;
+            }
+        }
+    }
+
+    /// The projection the skeleton is drawn with into a rect of `size` points,
+    /// from model coordinates to clip space (with `y` pointing up).
+    ///
+    /// NOTE: [`Scene::fit`] needs the bounds of the skeleton, so it is ignored
+    /// here; see [`Spine::view_matrix`] instead.
+    pub fn view_matrix(&self, size: egui::Vec2) -> Mat4 {
+        self.create_scene_view(size, None)
+    }
+
+    pub(crate) fn create_scene_view(&self, size: egui::Vec2, bounds: Option<egui::Rect>) -> Mat4 {
+        let (fit_scale, center) = match (self.fit, bounds) {
+            (Some(fit), Some(bounds)) if bounds.width() > 0. && bounds.height() > 0. => {
+                let available = (size - egui::Vec2::splat(self.padding * 2.)).max(egui::Vec2::ZERO);
+                let ratio = available / bounds.size();
+                let fit_scale = match fit {
+                    Fit::Contain => ratio.min_elem(),
+                    Fit::Cover => ratio.max_elem(),
+                };
+                (fit_scale, Vec2::new(bounds.center().x, bounds.center().y))
+            }
+            _ => (1., Vec2::ZERO),
+        };
+
         let position = self.position.extend(0.);
-        let scale = vec3(self.scale, self.scale, 1.);
+        let scale = self.scale * fit_scale;
+        let scale = vec3(scale, scale, 1.);
 
         let world = Mat4::from_translation(position)
             * Mat4::from_rotation_z(self.angle)
-            * Mat4::from_scale(scale);
+            * Mat4::from_scale(scale)
+            * Mat4::from_translation(-center.extend(0.));
 
         let (mut xl, mut xr) = (size.x * -0.5, size.x * 0.5);
         let (mut yl, mut yr) = (size.y * -0.5, size.y * 0.5);
@@ -206,11 +2384,127 @@ impl Default for Scene {
             angle: 0.0,
             scale: 1.0,
             reflect: Reflect::empty(),
+            flip_x: false,
+            flip_y: false,
+            fit: None,
+            padding: 0.0,
+            background: None,
+            checkerboard: None,
+            interactive: false,
+            depth: 0.0,
+        }
+    }
+}
+
+/// The usual transparency backdrop of image editors; see
+/// [`Scene::checkerboard`].
+///
+/// The squares are aligned to the top left corner of the rect, so they don't
+/// move along with [`Scene::position`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Checkerboard {
+    /// Side of each square, in points.
+    pub size: f32,
+    /// Color of the top left square.
+    pub color_a: egui::Color32,
+    pub color_b: egui::Color32,
+}
+
+impl Checkerboard {
+    #[cfg(any(feature = "wgpu", feature = "glow"))]
+    fn paint(&self, painter: &egui::Painter, rect: egui::Rect) {
+        painter.rect_filled(rect, 0.0, self.color_a);
+        if self.size <= 0.0 {
+            return;
+        }
+        let columns = (rect.width() / self.size).ceil() as usize;
+        let rows = (rect.height() / self.size).ceil() as usize;
+        let squares = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| (row, column)))
+            .filter(|(row, column)| (row + column) % 2 == 1)
+            .map(|(row, column)| {
+                let min = rect.min + egui::vec2(column as f32, row as f32) * self.size;
+                let square = egui::Rect::from_min_size(min, egui::Vec2::splat(self.size));
+                egui::Shape::rect_filled(square.intersect(rect), 0.0, self.color_b)
+            });
+        painter.extend(squares);
+    }
+}
+
+/// Outline drawn around the silhouette of the skeleton, behind it; see
+/// [`SpineOptions::outline`].
+///
+/// NOTE: The outline is made of copies of the skeleton filled with `color`,
+/// offset around it, so parts of the skeleton thinner than a fraction of
+/// `width` may have gaps between them and their outline.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Outline {
+    pub color: egui::Color32,
+    /// Distance from the skeleton to the outer edge of the outline, in points.
+    pub width: f32,
+}
+
+#[cfg(any(feature = "wgpu", feature = "glow"))]
+impl Outline {
+    /// Directions the skeleton is offset towards.
+    const DIRECTIONS: usize = 8;
+    /// Maximum number of offsets on every direction.
+    const MAX_RINGS: usize = 4;
+
+    /// Offsets the skeleton by `width` towards every direction, and by
+    /// fractions of it to fill the gaps in between.
+    fn pass(
+        &self,
+        size: egui::Vec2,
+        scene_view: Mat4,
+        color_space: ColorSpace,
+    ) -> Option<renderer::OutlinePass> {
+        if self.width <= 0.0 || self.color.a() == 0 || size.min_elem() <= 0.0 {
+            return None;
         }
+
+        let [r, g, b, a] = self.color.to_srgba_unmultiplied();
+        let color = match color_space {
+            ColorSpace::SRGB => [r, g, b].map(|c| f32::from(c) / 255.),
+            ColorSpace::Linear => [r, g, b].map(egui::ecolor::linear_f32_from_gamma_u8),
+        };
+        let color = [color[0], color[1], color[2], f32::from(a) / 255.];
+
+        let rings = ((self.width / 2.0).ceil() as usize).clamp(1, Self::MAX_RINGS);
+        let views = (1..=rings)
+            .flat_map(|ring| {
+                let radius = self.width * ring as f32 / rings as f32;
+                (0..Self::DIRECTIONS).map(move |direction| {
+                    let angle = std::f32::consts::TAU * direction as f32 / Self::DIRECTIONS as f32;
+                    Vec2::from_angle(angle) * radius
+                })
+            })
+            .map(|offset| {
+                // NOTE: Clip space spans 2 units along each axis of the rect.
+                let offset = offset * 2.0 / Vec2::new(size.x, size.y);
+                Mat4::from_translation(offset.extend(0.0)) * scene_view
+            })
+            .collect();
+
+        Some(renderer::OutlinePass { color, views })
     }
 }
 
+/// How [`Scene::fit`] scales the skeleton to the rect it is drawn into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fit {
+    /// The whole skeleton is visible.
+    Contain,
+    /// The skeleton covers the whole rect, possibly being cropped.
+    Cover,
+}
+
 bitflags::bitflags! {
+    /// Reflects the projection of the scene, without changing the skeleton.
+    ///
+    /// NOTE: Reflecting on a single axis reverses the winding of the drawn
+    /// triangles, so the face of [`Animation::cull_mode`] needs to be swapped
+    /// to keep culling the same triangles.
     #[derive(Clone, Copy, Debug)]
     pub struct Reflect: u8 {
         const XAxis = 0b01;
@@ -218,30 +2512,280 @@ bitflags::bitflags! {
     }
 }
 
+bitflags::bitflags! {
+    /// Overlays drawn with egui's painter, on top of the skeleton.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    pub struct DebugDraw: u8 {
+        /// The edges of every triangle of the visible attachments.
+        const Triangles = 0b01;
+        /// The bounds of the current pose; see [`Spine::bounds`].
+        const Bounds = 0b10;
+        /// The bones; see [`Spine::draw_bones`].
+        const Bones = 0b100;
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Animation {
-    // TODO(Unavailable): Option<>, to allow not showing anything, until a
-    // user changes it.
-    pub id: AnimationId,
+    /// Animation initially set on track `0`.
+    ///
+    /// When `None`, the skeleton stays in its setup pose until an animation is
+    /// set with one of the `Spine::set_animation_*` methods.
+    pub id: Option<AnimationId>,
     pub cull_mode: Option<Face>,
-    // TODO(Unavailable): Extra fields:
-    // ```
-    // playback_speed: f32,
-    // loop: bool,
-    // skin: Option<Skin>,
-    // crossfades: Option<Crossfades>
-    // ```
+    /// Multiplier applied to the frame time; `0.5` plays at half speed, `2.0`
+    /// at double speed.
+    ///
+    /// The Spine runtime doesn't support playing animations backwards by
+    /// feeding it negative deltas, so negative values are clamped to `0.0`.
+    ///
+    /// See [`Spine::set_track_timescale`] to change the speed of a single
+    /// track.
+    pub playback_speed: f32,
+    /// Whether the initial animation loops, or plays only once.
+    pub loop_: bool,
+    /// Name of the skin to use; when `None` the skeleton's default skin is
+    /// used.
+    pub skin: Option<Cow<'static, str>>,
 }
 
 impl Default for Animation {
     fn default() -> Self {
         Self {
-            id: AnimationId::Index(0),
+            id: Some(AnimationId::Index(0)),
             cull_mode: None,
+            playback_speed: 1.0,
+            loop_: true,
+            skin: None,
+        }
+    }
+}
+
+/// How the animation of a track is mixed with the pose of the lower tracks;
+/// see [`Spine::set_track_mix_blend`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MixBlend {
+    /// Mixes from the setup pose to the animation; the pose of the lower
+    /// tracks is discarded.
+    Setup,
+    /// Mixes from the current pose to the animation, using the setup pose
+    /// before the first key; meant for the first animations applied.
+    First,
+    /// Mixes from the current pose to the animation, keeping the current pose
+    /// before the first key; meant for animations layered on top of others.
+    #[default]
+    Replace,
+    /// Adds the animation on top of the current pose.
+    Add,
+}
+
+impl MixBlend {
+    fn into_c(self) -> rusty_spine::c::spMixBlend {
+        use rusty_spine::c::*;
+
+        match self {
+            MixBlend::Setup => SP_MIX_BLEND_SETUP,
+            MixBlend::First => SP_MIX_BLEND_FIRST,
+            MixBlend::Replace => SP_MIX_BLEND_REPLACE,
+            MixBlend::Add => SP_MIX_BLEND_ADD,
+        }
+    }
+}
+
+/// A mesh of the skeleton; see [`Spine::snapshot_meshes`].
+#[derive(Clone, Debug)]
+pub struct RenderableMesh {
+    pub vertices: Vec<Vertex>,
+    /// Triangle list indices into `vertices`.
+    pub indices: Vec<u16>,
+    pub blend_mode: BlendMode,
+    pub premultiplied_alpha: bool,
+    /// Name of the atlas page with the texture of the mesh, relative to the
+    /// atlas file.
+    pub texture_page: Option<String>,
+}
+
+/// A vertex of a [`RenderableMesh`]; also the layout of the vertex buffers
+/// used by the built-in renderers.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Pod, Zeroable)]
+pub struct Vertex {
+    /// Position in model space.
+    pub position: Vec2,
+    /// Texture coordinates inside the atlas page.
+    pub uv: Vec2,
+    /// Tint color, multiplied with the texture color.
+    pub color: Vec4,
+    /// Color of the darkest parts of the texture, for two-color tinting.
+    pub dark_color: Vec4,
+}
+
+impl Vertex {
+    pub fn new(position: Vec2, uv: Vec2, color: Vec4, dark_color: Vec4) -> Self {
+        Self {
+            position,
+            uv,
+            color,
+            dark_color,
+        }
+    }
+
+    pub(crate) fn from_renderable(renderable: &SkeletonCombinedRenderable) -> Vec<Self> {
+        (0..renderable.vertices.len())
+            .map(|index| Vertex {
+                position: Vec2::from_array(renderable.vertices[index]),
+                uv: Vec2::from_array(renderable.uvs[index]),
+                color: Vec4::from_array(renderable.colors[index]),
+                dark_color: Vec4::from_array(renderable.dark_colors[index]),
+            })
+            .collect()
+    }
+}
+
+/// Face of a vertex (cull mode)
+#[derive(Clone, Copy, Debug)]
+pub enum Face {
+    /// Front face
+    Front,
+    /// Back face
+    Back,
+}
+
+impl Face {
+    #[cfg(any(feature = "wgpu", feature = "glow"))]
+    fn flipped(self) -> Self {
+        match self {
+            Face::Front => Face::Back,
+            Face::Back => Face::Front,
+        }
+    }
+}
+
+/// Information about an animation available in the loaded skeleton.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnimationInfo {
+    pub name: String,
+    /// Index that can be used with [`AnimationId::Index`].
+    pub index: usize,
+    /// Duration in seconds.
+    pub duration: f32,
+}
+
+/// Mixes of a transform constraint, each from `0.0` to `1.0`; see
+/// [`Spine::set_transform_constraint_mixes`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TransformMix {
+    pub rotate: f32,
+    pub x: f32,
+    pub y: f32,
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub shear_y: f32,
+}
+
+impl TransformMix {
+    /// Every mix set to `mix`.
+    pub fn splat(mix: f32) -> Self {
+        Self {
+            rotate: mix,
+            x: mix,
+            y: mix,
+            scale_x: mix,
+            scale_y: mix,
+            shear_y: mix,
+        }
+    }
+}
+
+/// Size of what a [`Spine`] drew on a frame; see [`Spine::draw_stats`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DrawStats {
+    /// Meshes drawn, each one being a draw call.
+    pub meshes: usize,
+    pub vertices: usize,
+    pub indices: usize,
+    /// Distinct atlas pages the meshes were textured with.
+    pub textures: usize,
+}
+
+impl DrawStats {
+    #[cfg(any(feature = "wgpu", feature = "glow"))]
+    fn new(renderables: &[SkeletonCombinedRenderable]) -> Self {
+        let mut textures = Vec::new();
+        for renderable in renderables {
+            if let Some(texture) = renderable.attachment_renderer_object
+                && !textures.contains(&texture)
+            {
+                textures.push(texture);
+            }
+        }
+
+        Self {
+            meshes: renderables.len(),
+            vertices: renderables.iter().map(|r| r.vertices.len()).sum(),
+            indices: renderables.iter().map(|r| r.indices.len()).sum(),
+            textures: textures.len(),
         }
     }
 }
 
+/// Files a [`Spine`] was loaded from, to reload it.
+#[derive(Clone, Debug)]
+struct Source {
+    /// `None` if the atlas was loaded separately; see [`Spine::with_atlas`].
+    atlas: Option<PathBuf>,
+    skeleton: SkeletonKind<PathBuf>,
+}
+
+/// Files a [`Spine`] was loaded from; see [`Spine::source_paths`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourcePaths {
+    /// `None` if the atlas was loaded separately; see [`Spine::with_atlas`].
+    pub atlas: Option<PathBuf>,
+    pub skeleton: PathBuf,
+    /// Texture page of every page of the atlas, in the order they are listed
+    /// in the atlas file.
+    pub textures: Vec<PathBuf>,
+}
+
+impl SourcePaths {
+    /// Iterator over all the files; e.g. to watch them.
+    pub fn iter(&self) -> impl Iterator<Item = &Path> + '_ {
+        self.atlas
+            .iter()
+            .chain([&self.skeleton])
+            .chain(&self.textures)
+            .map(PathBuf::as_path)
+    }
+}
+
+/// Information about a slot of the skeleton, in its current pose.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlotInfo {
+    pub name: String,
+    /// Name of the attachment shown by the slot, if any.
+    pub attachment: Option<String>,
+    /// Tint of the attachment; see [`Spine::set_slot_color`].
+    pub color: egui::Color32,
+}
+
+/// Information about a page of the loaded atlas.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PageInfo {
+    /// Name of the texture file, relative to the atlas.
+    pub name: String,
+    /// Width in pixels, as declared by the atlas.
+    pub width: u32,
+    /// Height in pixels, as declared by the atlas.
+    pub height: u32,
+    pub min_filter: AtlasFilter,
+    pub mag_filter: AtlasFilter,
+    pub u_wrap: AtlasWrap,
+    pub v_wrap: AtlasWrap,
+    /// Whether the texture was exported with premultiplied alpha.
+    pub premultiplied_alpha: bool,
+}
+
 #[derive(Clone, Debug)]
 pub enum AnimationId {
     Index(usize),
@@ -253,3 +2797,260 @@ impl Default for AnimationId {
         Self::Index(0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use glam::Vec3;
+
+    const ATLAS: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/assets/spineboy/export/spineboy.atlas"
+    );
+    const SKELETON: &str = concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/assets/spineboy/export/spineboy-ess.json"
+    );
+
+    fn spineboy(options: SpineOptions) -> Spine {
+        Spine::new(ATLAS, SkeletonKind::Json(SKELETON), options).unwrap()
+    }
+
+    #[test]
+    fn updates_without_renderer() {
+        let skeleton = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/spineboy/export/spineboy-pro.json"
+        );
+        let options = SpineOptions::builder()
+            .animation_name("run")
+            .queue_events(true)
+            .build();
+        let mut spine = Spine::new(ATLAS, SkeletonKind::Json(skeleton), options).unwrap();
+        for _ in 0..60 {
+            spine.update(1.0 / 60.0).unwrap();
+        }
+
+        let events = spine.drain_events();
+        assert_eq!(
+            events.first().map(|event| &event.kind),
+            Some(&SpineEventKind::Start)
+        );
+        assert!(events.iter().any(|event| matches!(
+            &event.kind,
+            SpineEventKind::Event { name, .. } if name == "footstep"
+        )));
+        assert!(spine.bounds().is_some());
+    }
+
+    #[test]
+    fn triangle_contains_points() {
+        let triangle = [Vec2::ZERO, Vec2::new(2., 0.), Vec2::new(0., 2.)];
+
+        assert!(triangle_contains(Vec2::new(0.5, 0.5), triangle));
+        assert!(!triangle_contains(Vec2::new(2., 2.), triangle));
+        assert!(!triangle_contains(Vec2::new(-0.5, 0.5), triangle));
+        // NOTE: Regardless of the winding order.
+        assert!(triangle_contains(
+            Vec2::new(0.5, 0.5),
+            [triangle[0], triangle[2], triangle[1]]
+        ));
+    }
+
+    #[test]
+    fn triangle_contains_edges() {
+        let triangle = [Vec2::ZERO, Vec2::new(2., 0.), Vec2::new(0., 2.)];
+
+        assert!(triangle_contains(Vec2::new(1., 0.), triangle));
+        assert!(triangle_contains(Vec2::new(1., 1.), triangle));
+        assert!(triangle_contains(Vec2::new(2., 0.), triangle));
+    }
+
+    #[test]
+    fn triangle_contains_nothing_if_degenerate() {
+        let point = [Vec2::ONE; 3];
+        let line = [Vec2::ZERO, Vec2::ONE, Vec2::splat(2.)];
+
+        assert!(!triangle_contains(Vec2::ONE, point));
+        assert!(!triangle_contains(Vec2::ONE, line));
+        assert!(!triangle_contains(Vec2::new(0.5, 0.5), line));
+    }
+
+    #[test]
+    fn hit_test_skips_transparent_slots() {
+        let mut spine = spineboy(SpineOptions::default());
+        spine.update(0.0).unwrap();
+        let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, egui::vec2(800., 800.));
+        let bounds = spine.bounds().unwrap();
+        let point = spine.model_to_screen(Vec2::new(bounds.center().x, bounds.center().y), rect);
+        let slot = spine.hit_slot(point, rect).unwrap();
+
+        spine
+            .set_slot_color(&slot, egui::Color32::TRANSPARENT)
+            .unwrap();
+        assert_ne!(spine.hit_slot(point, rect), Some(slot));
+    }
+
+    #[test]
+    fn on_event_only_filters_callback() {
+        let skeleton = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/assets/spineboy/export/spineboy-pro.json"
+        );
+        let options = SpineOptions::builder()
+            .animation_name("run")
+            .queue_events(true)
+            .build();
+        let mut spine = Spine::new(ATLAS, SkeletonKind::Json(skeleton), options).unwrap();
+        let (sender, receiver) = std::sync::mpsc::channel();
+        spine.on_event(Some(0), EventKinds::Event, move |event| {
+            sender.send(event).unwrap();
+        });
+        for _ in 0..60 {
+            spine.update(1.0 / 60.0).unwrap();
+        }
+
+        let called: Vec<_> = receiver.try_iter().collect();
+        assert!(!called.is_empty());
+        assert!(
+            called
+                .iter()
+                .all(|event| matches!(event.kind, SpineEventKind::Event { .. }))
+        );
+        let queued = spine.drain_events();
+        assert_eq!(
+            queued.first().map(|event| &event.kind),
+            Some(&SpineEventKind::Start)
+        );
+        assert!(queued.len() > called.len());
+    }
+
+    #[test]
+    fn reload_keeps_tracks() {
+        let mut spine = spineboy(SpineOptions::builder().animation_name("run").build());
+        spine.update(0.25).unwrap();
+        spine.set_track_mix_blend(0, MixBlend::Add).unwrap();
+        spine.set_track_timescale(0, 0.5).unwrap();
+        spine.reload().unwrap();
+
+        let track = spine
+            .controller
+            .animation_state
+            .tracks()
+            .flatten()
+            .next()
+            .unwrap();
+        assert_eq!(track.animation().name(), "run");
+        assert_eq!(track.track_time(), 0.25);
+        assert_eq!(track.timescale(), 0.5);
+        // SAFETY: The pointer comes from a live track entry.
+        let mix_blend = unsafe { (*track.c_ptr()).mixBlend };
+        assert_eq!(mix_blend, MixBlend::Add.into_c());
+    }
+
+    #[test]
+    fn busy_while_rendered() {
+        let mut spine = spineboy(SpineOptions::default());
+        let render = Arc::clone(&spine.controller);
+
+        assert!(matches!(spine.update(0.0), Err(SpineError::Busy)));
+        drop(render);
+        assert!(spine.update(0.0).is_ok());
+        assert!(matches!(
+            spine.set_skin("missing"),
+            Err(SpineError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn ik_target_of_root_bone_follows_flipped_skeleton() {
+        // NOTE: None of the example skeletons targets a root bone.
+        let skeleton = std::env::temp_dir().join("egui_spine_root_ik_target.json");
+        std::fs::write(
+            &skeleton,
+            r#"{
+                "skeleton": { "spine": "4.2.00" },
+                "bones": [
+                    { "name": "root" },
+                    { "name": "arm", "parent": "root", "length": 10 }
+                ],
+                "ik": [{ "name": "aim", "bones": ["arm"], "target": "root" }],
+                "animations": { "idle": {} }
+            }"#,
+        )
+        .unwrap();
+        let options = SpineOptions::builder().flip_x(true).build();
+        let mut spine = Spine::new(
+            ATLAS,
+            SkeletonKind::Json(skeleton.to_str().unwrap()),
+            options,
+        )
+        .unwrap();
+        spine.update(0.).unwrap();
+
+        let target = vec2(30., -20.);
+        spine.set_ik_target("aim", target).unwrap();
+        spine.update(0.).unwrap();
+        let position = spine.bone_world_position("root").unwrap();
+        assert!(position.abs_diff_eq(target, 1e-4), "{position}");
+    }
+
+    #[test]
+    #[cfg(all(feature = "wgpu", feature = "image"))]
+    fn copy_slots_into_clone() {
+        let mut spine = spineboy(SpineOptions::default());
+        spine.set_slot_color("head", egui::Color32::RED).unwrap();
+        spine.set_attachment("gun", None).unwrap();
+        let mut clone = spine.clone_instance().unwrap();
+        spine.copy_slots_into(&mut clone).unwrap();
+
+        let slot = |spine: &Spine, name: &str| spine.slots().into_iter().find(|s| s.name == name);
+        for name in ["head", "gun"] {
+            assert_eq!(slot(&clone, name), slot(&spine, name));
+        }
+        assert_eq!(slot(&clone, "gun").unwrap().attachment, None);
+    }
+
+    #[test]
+    fn view_matrix_ignores_depth() {
+        let size = egui::vec2(640., 480.);
+        let scene = Scene {
+            depth: 0.5,
+            ..Default::default()
+        };
+        let view = scene.view_matrix(size);
+
+        assert_eq!(view, Scene::default().view_matrix(size));
+        assert_eq!(view.project_point3(Vec3::ZERO).z, 0.);
+    }
+
+    #[test]
+    fn pose_hash_is_stable() {
+        let run = || {
+            let mut spine = spineboy(SpineOptions::builder().animation_name("run").build());
+            for _ in 0..10 {
+                spine.update(1.0 / 30.0).unwrap();
+            }
+            spine
+        };
+        let mut spine = run();
+        let hash = spine.pose_hash().unwrap();
+
+        assert_eq!(hash, 0x4dca_0089_0d07_07ea);
+        assert_eq!(run().pose_hash().unwrap(), hash);
+
+        spine.update(1.0 / 30.0).unwrap();
+        assert_ne!(spine.pose_hash().unwrap(), hash);
+    }
+
+    #[test]
+    fn pose_hash_includes_tint() {
+        let hash = |tint| {
+            let options = SpineOptions::builder().animation_name("run").tint(tint);
+            let mut spine = spineboy(options.build());
+            spine.update(1.0 / 30.0).unwrap();
+            spine.pose_hash().unwrap()
+        };
+        assert_ne!(hash(egui::Color32::WHITE), hash(egui::Color32::RED));
+    }
+}
@@ -1,25 +1,409 @@
-use bytemuck::{Pod, Zeroable};
-use glam::{Mat4, Vec2, Vec4};
+use crate::Face;
+pub use crate::Vertex;
+use glam::Mat4;
 use rusty_spine::BlendMode;
+#[cfg(not(target_arch = "wasm32"))]
+use std::collections::VecDeque;
+use std::{
+    error::Error,
+    fmt, io,
+    sync::{
+        Arc, Mutex, PoisonError,
+        atomic::{AtomicU8, AtomicUsize, Ordering},
+        mpsc::{self, TryRecvError},
+    },
+    time::{Duration, Instant},
+};
 
+#[cfg(feature = "glow")]
+pub mod glow;
+#[cfg(feature = "wgpu")]
+mod ktx2;
+#[cfg(feature = "wgpu")]
 pub mod wgpu;
 
+/// Backend used to paint every `Spine`; set when its context is initialized.
+static BACKEND: AtomicU8 = AtomicU8::new(Backend::DEFAULT as u8);
+/// Number of texture pages whose loading didn't finish yet.
+///
+/// NOTE: It is decremented by the workers, so that pages that are never drawn
+/// (and so never polled) don't keep the widgets repainting.
+static PENDING_TEXTURES: AtomicUsize = AtomicUsize::new(0);
+/// Jobs waiting for a worker; see [`PendingTexture::spawn`].
+#[cfg(not(target_arch = "wasm32"))]
+static TEXTURE_JOBS: Mutex<TextureJobs> = Mutex::new(TextureJobs {
+    queue: VecDeque::new(),
+    workers: 0,
+});
+/// Earliest time a texture page that failed to load should be loaded again.
+static TEXTURE_RETRY: Mutex<Option<Instant>> = Mutex::new(None);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum Backend {
+    #[cfg(feature = "wgpu")]
+    Wgpu,
+    #[cfg(feature = "glow")]
+    Glow,
+}
+
+impl Backend {
+    #[cfg(feature = "wgpu")]
+    const DEFAULT: Self = Self::Wgpu;
+    #[cfg(not(feature = "wgpu"))]
+    const DEFAULT: Self = Self::Glow;
+
+    fn set(self) {
+        BACKEND.store(self as u8, Ordering::Relaxed);
+    }
+
+    fn get() -> Self {
+        match BACKEND.load(Ordering::Relaxed) {
+            #[cfg(feature = "wgpu")]
+            backend if backend == Self::Wgpu as u8 => Self::Wgpu,
+            #[cfg(feature = "glow")]
+            backend if backend == Self::Glow as u8 => Self::Glow,
+            _ => Self::DEFAULT,
+        }
+    }
+}
+
+/// Creates the paint callback of the initialized backend.
+pub fn paint_callback(rect: egui::Rect, callback: RendererCallback) -> egui::PaintCallback {
+    match Backend::get() {
+        #[cfg(feature = "wgpu")]
+        Backend::Wgpu => egui_wgpu::Callback::new_paint_callback(rect, callback),
+        #[cfg(feature = "glow")]
+        Backend::Glow => glow::paint_callback(rect, callback),
+    }
+}
+
+/// Reads the bytes of the texture page found at the given path.
+///
+/// The path is the directory of the atlas, made absolute, joined with the
+/// name of the page.
+pub type TextureLoader = dyn Fn(&str) -> io::Result<Vec<u8>> + Send + Sync + 'static;
+/// Decodes the bytes of a texture page into its width, height and RGBA8
+/// pixels.
+pub type TextureDecoder =
+    dyn Fn(&[u8]) -> Result<(u32, u32, Vec<u8>), TextureError> + Send + Sync + 'static;
+
+/// Error while loading the texture of an atlas page.
+#[derive(Debug)]
+pub enum TextureError {
+    /// The texture page couldn't be read.
+    Io(io::Error),
+    /// The texture page couldn't be decoded.
+    Decode(Box<dyn Error + Send + Sync + 'static>),
+}
+
+impl fmt::Display for TextureError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "failed to read texture: {error}"),
+            Self::Decode(error) => write!(f, "failed to decode texture: {error}"),
+        }
+    }
+}
+
+impl Error for TextureError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Decode(error) => Some(error.as_ref()),
+        }
+    }
+}
+
+impl From<io::Error> for TextureError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<image::ImageError> for TextureError {
+    fn from(error: image::ImageError) -> Self {
+        Self::Decode(Box::new(error))
+    }
+}
+
+/// The default [`TextureDecoder`]; requires the `image` feature.
+pub(crate) fn decode_texture(bytes: &[u8]) -> Result<(u32, u32, Vec<u8>), TextureError> {
+    #[cfg(feature = "image")]
+    {
+        let pixels = image::load_from_memory(bytes)?.to_rgba8();
+        let (width, height) = pixels.dimensions();
+        Ok((width, height, pixels.into_raw()))
+    }
+    #[cfg(not(feature = "image"))]
+    {
+        let _ = bytes;
+        Err(TextureError::Decode(
+            "enable the `image` feature or provide a `texture_decoder`".into(),
+        ))
+    }
+}
+
+/// Decodes `bytes` with `decoder`, checking that the pixels match the size.
+fn checked_decode(
+    decoder: &TextureDecoder,
+    bytes: &[u8],
+) -> Result<(u32, u32, Vec<u8>), TextureError> {
+    let (width, height, pixels) = decoder(bytes)?;
+    // NOTE: Uploading less pixels than the texture size would panic.
+    let expected_len = width as usize * height as usize * 4;
+    if pixels.len() != expected_len {
+        return Err(TextureError::Decode(
+            format!(
+                "decoded {} bytes for a {width}x{height} texture, expected {expected_len}",
+                pixels.len()
+            )
+            .into(),
+        ));
+    }
+
+    Ok((width, height, pixels))
+}
+
+/// Premultiplies the alpha of `pixels` in linear space instead, for sRGB
+/// targets, which blend in linear space; atlases are premultiplied in gamma
+/// space.
+// TODO(Unavailable): Rewrite with `epaint`.
+pub(super) fn premultiply_in_linear_space(pixels: &mut [u8]) {
+    use bevy_color::{LinearRgba, Srgba};
+
+    for pixel in pixels.chunks_exact_mut(4) {
+        let srgba = Srgba::rgba_u8(pixel[0], pixel[1], pixel[2], pixel[3]);
+        let srgba = if srgba.alpha != 0. {
+            Srgba::new(
+                srgba.red / srgba.alpha,
+                srgba.green / srgba.alpha,
+                srgba.blue / srgba.alpha,
+                srgba.alpha,
+            )
+        } else {
+            Srgba::new(0., 0., 0., 0.)
+        };
+        let mut lrgba = LinearRgba::from(srgba);
+        lrgba.red *= lrgba.alpha;
+        lrgba.green *= lrgba.alpha;
+        lrgba.blue *= lrgba.alpha;
+        let srgba = Srgba::from(lrgba);
+        // NOTE: Rounded, so that the round trip doesn't darken opaque pixels.
+        pixel[0] = (srgba.red * 255.).round() as u8;
+        pixel[1] = (srgba.green * 255.).round() as u8;
+        pixel[2] = (srgba.blue * 255.).round() as u8;
+        pixel[3] = (srgba.alpha * 255.).round() as u8;
+    }
+}
+
+/// How texture pages are loaded, shared by the texture callbacks and the
+/// backend resources.
+#[derive(Clone)]
+pub(super) struct TextureLoading {
+    pub loader: Arc<TextureLoader>,
+    pub decoder: Arc<TextureDecoder>,
+    pub retry_interval: Duration,
+    pub max_retries: u32,
+}
+
+impl TextureLoading {
+    /// Whether a texture page that failed to load `attempts` times, the last
+    /// one at `last_try`, should be loaded again.
+    ///
+    /// NOTE: The interval is doubled after every attempt.
+    fn should_retry(&self, attempts: u32, last_try: Instant) -> bool {
+        if attempts > self.max_retries {
+            return false;
+        }
+        let backoff = self
+            .retry_interval
+            .saturating_mul(1 << attempts.saturating_sub(1).min(16));
+        let is_due = last_try.elapsed() >= backoff;
+        if !is_due {
+            // NOTE: Nothing might be painted until then, e.g. if the scene is
+            // paused, so the widget requests a repaint for it.
+            let retry_at = last_try + backoff;
+            let mut next_retry = TEXTURE_RETRY.lock().unwrap_or_else(PoisonError::into_inner);
+            *next_retry = Some(next_retry.map_or(retry_at, |next| next.min(retry_at)));
+        }
+        is_due
+    }
+
+    /// Like [`TextureLoading::should_retry`], for a texture page that just
+    /// failed to load, to schedule its next attempt.
+    fn failed(&self, attempts: u32) -> Instant {
+        let last_try = Instant::now();
+        self.should_retry(attempts, last_try);
+        last_try
+    }
+}
+
+/// How long until a texture page that failed to load is loaded again, so that
+/// a repaint is requested for it.
+pub(crate) fn take_texture_retry() -> Option<Duration> {
+    let next_retry = TEXTURE_RETRY
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .take()?;
+    Some(next_retry.saturating_duration_since(Instant::now()))
+}
+
+/// Whether any texture page is still being loaded, so that a repaint is needed
+/// to draw it once it is ready.
+pub(crate) fn is_loading_textures() -> bool {
+    PENDING_TEXTURES.load(Ordering::Relaxed) > 0
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+struct TextureJobs {
+    queue: VecDeque<Box<dyn FnOnce() + Send>>,
+    /// Number of worker threads currently running.
+    workers: usize,
+}
+
+/// Maximum number of threads loading texture pages at the same time.
+#[cfg(not(target_arch = "wasm32"))]
+const MAX_TEXTURE_WORKERS: usize = 4;
+
+/// Runs `job` on one of the texture workers, starting one if there are less
+/// than [`MAX_TEXTURE_WORKERS`]; workers exit once there are no jobs left.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_texture_job(job: Box<dyn FnOnce() + Send>) {
+    fn work() {
+        loop {
+            let job = {
+                let mut jobs = TEXTURE_JOBS.lock().unwrap_or_else(PoisonError::into_inner);
+                match jobs.queue.pop_front() {
+                    Some(job) => job,
+                    None => {
+                        jobs.workers -= 1;
+                        return;
+                    }
+                }
+            };
+            // NOTE: A panicking loader only fails its texture, which is
+            // reported when the page is polled.
+            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(job));
+        }
+    }
+
+    let mut jobs = TEXTURE_JOBS.lock().unwrap_or_else(PoisonError::into_inner);
+    jobs.queue.push_back(job);
+    if jobs.workers < MAX_TEXTURE_WORKERS {
+        jobs.workers += 1;
+        std::thread::spawn(work);
+    }
+}
+
+/// A texture page being read, and decoded, on a worker thread; so that
+/// painting never waits for the filesystem.
+pub(super) struct PendingTexture<T>(mpsc::Receiver<Result<T, TextureError>>);
+
+impl<T: Send + 'static> PendingTexture<T> {
+    /// Runs `load` on a worker thread; at most [`MAX_TEXTURE_WORKERS`] pages
+    /// are loaded at the same time, and the rest wait for their turn.
+    ///
+    /// NOTE: `wasm32` can't spawn threads, so the texture is loaded right away.
+    fn spawn<F>(load: F) -> Self
+    where
+        F: FnOnce() -> Result<T, TextureError> + Send + 'static,
+    {
+        /// Marks the load as finished, even if it panics.
+        struct Finished;
+        impl Drop for Finished {
+            fn drop(&mut self) {
+                PENDING_TEXTURES.fetch_sub(1, Ordering::Relaxed);
+            }
+        }
+
+        let (sender, receiver) = mpsc::sync_channel(1);
+        PENDING_TEXTURES.fetch_add(1, Ordering::Relaxed);
+        let job = move || {
+            let _finished = Finished;
+            // NOTE: The atlas page might have been disposed while loading.
+            let _ = sender.send(load());
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        run_texture_job(Box::new(job));
+        #[cfg(target_arch = "wasm32")]
+        job();
+
+        Self(receiver)
+    }
+
+    /// Blocks until the texture is loaded.
+    #[cfg(feature = "wgpu")]
+    fn wait(&self) -> Result<T, TextureError> {
+        self.0
+            .recv()
+            .unwrap_or_else(|_| Err(io::Error::other("texture loading thread panicked").into()))
+    }
+
+    /// Returns the loaded texture, if it is ready.
+    fn poll(&self) -> Option<Result<T, TextureError>> {
+        match self.0.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => Some(Err(io::Error::other(
+                "texture loading thread panicked",
+            )
+            .into())),
+        }
+    }
+}
+
 pub struct RendererCallback {
     pub meshes: Meshes,
     pub scene_view: Mat4,
     pub cull_mode: Option<Face>,
+    pub desaturate: f32,
+    /// Depth the meshes are drawn at; see `Scene::depth`.
+    #[cfg(feature = "wgpu")]
+    pub depth: f32,
+    pub outline: Option<OutlinePass>,
+    /// Scenes and meshes that can be drawn, set on `prepare`.
+    #[cfg(feature = "wgpu")]
+    prepared: Mutex<wgpu::PreparedCallback>,
 }
 
-#[repr(C)]
-#[derive(Clone, Copy, Debug, Pod, Zeroable)]
-pub struct Vertex {
-    position: Vec2,
-    uv: Vec2,
-    color: Vec4,
-    dark_color: Vec4,
+impl RendererCallback {
+    pub fn new(
+        meshes: Meshes,
+        scene_view: Mat4,
+        cull_mode: Option<Face>,
+        desaturate: f32,
+        depth: f32,
+        outline: Option<OutlinePass>,
+    ) -> Self {
+        // NOTE: Only the `wgpu` renderer depth tests.
+        #[cfg(not(feature = "wgpu"))]
+        let _ = depth;
+        Self {
+            meshes,
+            scene_view,
+            cull_mode,
+            desaturate: desaturate.clamp(0.0, 1.0),
+            #[cfg(feature = "wgpu")]
+            depth: depth.clamp(0.0, 1.0),
+            outline,
+            #[cfg(feature = "wgpu")]
+            prepared: Mutex::default(),
+        }
+    }
+}
+
+/// Silhouette of the meshes, filled with `color` and drawn behind them once for
+/// every scene view in `views`.
+pub struct OutlinePass {
+    /// Straight alpha color, in the color space of the vertex colors.
+    pub color: [f32; 4],
+    pub views: Vec<Mat4>,
 }
 
 impl Vertex {
+    #[cfg(feature = "wgpu")]
     pub fn wgpu_buffer_layout() -> wgpu::VertexBufferLayout<'static> {
         use wgpu::*;
 
@@ -34,23 +418,53 @@ impl Vertex {
     }
 }
 
+/// Blend state of a mesh, independent of the graphics API; colors are always
+/// added.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BlendState {
+    pub color: BlendComponent,
+    pub alpha: BlendComponent,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BlendComponent {
+    pub src_factor: BlendFactor,
+    pub dst_factor: BlendFactor,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlendFactor {
+    One,
+    OneMinusSrc,
+    SrcAlpha,
+    OneMinusSrcAlpha,
+    Dst,
+}
+
+/// Blend mode of a mesh, which is converted into the blend state used by the
+/// built-in renderers.
 #[derive(Clone, Copy, Debug)]
 pub struct SpineBlendMode(BlendMode);
 
+impl From<BlendMode> for SpineBlendMode {
+    fn from(blend_mode: BlendMode) -> Self {
+        Self(blend_mode)
+    }
+}
+
 impl SpineBlendMode {
-    fn into_blend_state(self, premultiplied_alpha: bool) -> wgpu::BlendState {
-        use wgpu::*;
+    /// The blend state used by the built-in renderers, independent of the
+    /// graphics API; see `SpineBlendMode::into_blend_state` for `wgpu`'s.
+    pub fn into_spine_blend_state(self, premultiplied_alpha: bool) -> BlendState {
         match self.0 {
             BlendMode::Additive => match premultiplied_alpha {
                 // Case 1: Additive Blend Mode, Normal Alpha
                 false => BlendState {
                     alpha: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::One,
                         dst_factor: BlendFactor::One,
                     },
                     color: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::SrcAlpha,
                         dst_factor: BlendFactor::One,
                     },
@@ -58,12 +472,10 @@ impl SpineBlendMode {
                 // Case 2: Additive Blend Mode, Premultiplied Alpha
                 true => BlendState {
                     alpha: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::One,
                         dst_factor: BlendFactor::One,
                     },
                     color: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::One,
                         dst_factor: BlendFactor::One,
                     },
@@ -73,12 +485,10 @@ impl SpineBlendMode {
                 // Case 3: Multiply Blend Mode, Normal Alpha
                 false => BlendState {
                     alpha: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::OneMinusSrcAlpha,
                         dst_factor: BlendFactor::OneMinusSrcAlpha,
                     },
                     color: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::Dst,
                         dst_factor: BlendFactor::OneMinusSrcAlpha,
                     },
@@ -86,12 +496,10 @@ impl SpineBlendMode {
                 // Case 4: Multiply Blend Mode, Premultiplied Alpha
                 true => BlendState {
                     alpha: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::OneMinusSrcAlpha,
                         dst_factor: BlendFactor::OneMinusSrcAlpha,
                     },
                     color: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::Dst,
                         dst_factor: BlendFactor::OneMinusSrcAlpha,
                     },
@@ -101,12 +509,10 @@ impl SpineBlendMode {
                 // Case 5: Normal Blend Mode, Normal Alpha
                 false => BlendState {
                     alpha: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::One,
                         dst_factor: BlendFactor::OneMinusSrcAlpha,
                     },
                     color: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::SrcAlpha,
                         dst_factor: BlendFactor::OneMinusSrcAlpha,
                     },
@@ -114,12 +520,10 @@ impl SpineBlendMode {
                 // Case 6: Normal Blend Mode, Premultiplied Alpha
                 true => BlendState {
                     alpha: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::One,
                         dst_factor: BlendFactor::OneMinusSrcAlpha,
                     },
                     color: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::One,
                         dst_factor: BlendFactor::OneMinusSrcAlpha,
                     },
@@ -129,12 +533,10 @@ impl SpineBlendMode {
                 // Case 7: Screen Blend Mode, Normal Alpha
                 false => BlendState {
                     alpha: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::OneMinusSrc,
                         dst_factor: BlendFactor::OneMinusSrcAlpha,
                     },
                     color: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::One,
                         dst_factor: BlendFactor::OneMinusSrcAlpha,
                     },
@@ -142,12 +544,10 @@ impl SpineBlendMode {
                 // Case 8: Screen Blend Mode, Premultiplied Alpha
                 true => BlendState {
                     alpha: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::OneMinusSrc,
                         dst_factor: BlendFactor::OneMinusSrcAlpha,
                     },
                     color: BlendComponent {
-                        operation: BlendOperation::Add,
                         src_factor: BlendFactor::One,
                         dst_factor: BlendFactor::OneMinusSrcAlpha,
                     },
@@ -157,27 +557,8 @@ impl SpineBlendMode {
     }
 }
 
-/// Face of a vertex (cull mode)
-#[derive(Clone, Copy, Debug)]
-pub enum Face {
-    /// Front face
-    Front,
-    /// Back face
-    Back,
-}
-
-impl Face {
-    pub fn into_wgpu_face(self) -> wgpu::WgpuFace {
-        match self {
-            Face::Front => wgpu::WgpuFace::Front,
-            Face::Back => wgpu::WgpuFace::Back,
-        }
-    }
-}
-
 mod meshes {
     use super::{SpineBlendMode, Vertex};
-    use glam::{Vec2, Vec4};
     use rusty_spine::c::c_void;
     use rusty_spine::controller::{SkeletonCombinedRenderable, SkeletonController};
     use std::{cell::Cell, sync::Arc};
@@ -199,26 +580,17 @@ mod meshes {
             }
         }
 
-        pub fn iter(&self) -> impl Iterator<Item = Mesh> {
-            self.inner.iter().map(|renderable| {
-                let vertices_len = renderable.vertices.len();
-                let mut vertices = Vec::with_capacity(vertices_len);
-                for vertex_index in 0..vertices_len {
-                    vertices.push(Vertex {
-                        position: Vec2::from_array(renderable.vertices[vertex_index]),
-                        uv: Vec2::from_array(renderable.uvs[vertex_index]),
-                        color: Vec4::from_array(renderable.colors[vertex_index]),
-                        dark_color: Vec4::from_array(renderable.dark_colors[vertex_index]),
-                    });
-                }
-                Mesh {
-                    vertices,
-                    indices: &renderable.indices,
-                    blend_mode: SpineBlendMode(renderable.blend_mode),
-                    premultiplied_alpha: renderable.premultiplied_alpha,
-                    attachment: renderable.attachment_renderer_object,
-                    was_attachment_borrowed: Cell::new(false),
-                }
+        /// NOTE: Consecutive renderables sharing the same texture and blend
+        /// mode are already merged by `SkeletonController::combined_renderables`
+        /// (keeping the draw order), so each mesh is a single draw call.
+        pub fn iter(&self) -> impl Iterator<Item = Mesh<'_>> {
+            self.inner.iter().map(|renderable| Mesh {
+                vertices: Vertex::from_renderable(renderable),
+                indices: &renderable.indices,
+                blend_mode: SpineBlendMode(renderable.blend_mode),
+                premultiplied_alpha: renderable.premultiplied_alpha,
+                attachment: renderable.attachment_renderer_object,
+                was_attachment_borrowed: Cell::new(false),
             })
         }
     }
@@ -244,6 +616,7 @@ mod meshes {
         ///
         /// [`renderer_object`]: rusty_spine::atlas::AtlasPage::renderer_object
         /// [`set_create_texture_cb`]: rusty_spine::extension::set_create_texture_cb
+        #[allow(clippy::mut_from_ref)]
         pub unsafe fn renderer_object<T>(&self) -> Option<&mut T> {
             if self.was_attachment_borrowed.replace(true) {
                 panic!("Whoever is modifying the wgpu module made an oopsie daisy :)");
@@ -285,4 +658,45 @@ mod meshes {
     unsafe impl Sync for Meshes {}
 }
 // NOTE: Prevents submodules from accessing fields.
+#[cfg(feature = "glow")]
+pub use meshes::Mesh;
 pub use meshes::Meshes;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pending_textures_finish_without_being_polled() {
+        let pending: Vec<_> = (0..MAX_TEXTURE_WORKERS * 2)
+            .map(|i| PendingTexture::spawn(move || Ok(i)))
+            .collect();
+        let start = Instant::now();
+        while is_loading_textures() {
+            assert!(start.elapsed() < Duration::from_secs(5));
+            std::thread::yield_now();
+        }
+
+        for (i, pending) in pending.iter().enumerate() {
+            assert_eq!(pending.poll().unwrap().unwrap(), i);
+        }
+    }
+
+    #[test]
+    fn premultiply_in_linear_space_keeps_opaque_and_clear_pixels() {
+        let mut pixels = vec![200, 100, 50, 255, 0, 0, 0, 0, 90, 90, 90, 0];
+        premultiply_in_linear_space(&mut pixels);
+
+        assert_eq!(pixels, [200, 100, 50, 255, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn premultiply_in_linear_space_is_brighter_than_srgb_premultiply() {
+        // NOTE: White at half alpha, premultiplied in gamma space.
+        let mut pixels = vec![128, 128, 128, 128];
+        premultiply_in_linear_space(&mut pixels);
+
+        assert!(pixels[..3].iter().all(|&channel| channel > 128));
+        assert_eq!(pixels[3], 128);
+    }
+}
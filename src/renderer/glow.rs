@@ -0,0 +1,629 @@
+use super::{
+    Backend, BlendFactor, BlendState, Mesh, PendingTexture, RendererCallback, SpineBlendMode,
+    TextureDecoder, TextureError, TextureLoader, TextureLoading, Vertex,
+};
+use crate::Face;
+use egui_glow::{
+    ShaderVersion,
+    glow::{self, HasContext},
+};
+use rusty_spine::{
+    BlendMode,
+    atlas::{AtlasFilter, AtlasWrap},
+};
+use std::{
+    io,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    time::{Duration, Instant},
+};
+
+/// Resources shared by every paint callback.
+///
+/// NOTE: `egui_glow` has no equivalent of `egui_wgpu`'s callback resources.
+static RESOURCES: Mutex<Option<GlowResources>> = Mutex::new(None);
+/// Textures of disposed atlas pages, deleted on the next paint.
+///
+/// NOTE: The dispose callback doesn't have access to the `glow::Context`.
+static DISPOSED_TEXTURES: Mutex<Vec<glow::Texture>> = Mutex::new(Vec::new());
+
+pub struct GlowContextOptions {
+    pub on_texture_load_error: Option<Box<dyn Fn(TextureError) + Send + Sync + 'static>>,
+    /// Reads the bytes of the texture page found at the given path.
+    ///
+    /// Defaults to reading from the filesystem.
+    pub texture_loader: Box<TextureLoader>,
+    /// Decodes the bytes read by `texture_loader`.
+    ///
+    /// Defaults to decoding with the `image` crate, if the `image` feature is
+    /// enabled.
+    pub texture_decoder: Box<TextureDecoder>,
+    /// Time to wait before loading again a texture page that failed to load,
+    /// e.g. because it is still being downloaded; doubled after every retry.
+    pub texture_retry_interval: Duration,
+    /// How many times a texture page is loaded again before giving up.
+    pub max_texture_retries: u32,
+    /// Filter used for atlas pages exported with an unsupported filter mode;
+    /// either `glow::NEAREST` or `glow::LINEAR`.
+    pub fallback_filter: u32,
+    /// Filter used for every atlas page, regardless of the filter modes they
+    /// were exported with; either `glow::NEAREST` (e.g. for pixel art) or
+    /// `glow::LINEAR`.
+    pub filter_override: Option<u32>,
+}
+
+impl Default for GlowContextOptions {
+    fn default() -> Self {
+        Self {
+            on_texture_load_error: Some(Box::new(|error| {
+                log::error!("Error while loading texture: {error:?}");
+            })),
+            texture_loader: Box::new(|path| std::fs::read(path)),
+            texture_decoder: Box::new(super::decode_texture),
+            texture_retry_interval: Duration::from_secs(1),
+            max_texture_retries: 4,
+            fallback_filter: glow::LINEAR,
+            filter_override: None,
+        }
+    }
+}
+
+/// Initializes the `glow` backend.
+///
+/// Requires vertex array objects, so OpenGL 3.0, OpenGL ES 3.0 or WebGL 2.
+///
+/// # Errors
+///
+/// If the shaders failed to compile or link.
+pub fn init_glow_spine_context(
+    gl: &glow::Context,
+    options: GlowContextOptions,
+) -> Result<(), String> {
+    let GlowContextOptions {
+        on_texture_load_error,
+        texture_loader,
+        texture_decoder,
+        texture_retry_interval,
+        max_texture_retries,
+        fallback_filter,
+        filter_override,
+    } = options;
+    let texture_loading = TextureLoading {
+        loader: texture_loader.into(),
+        decoder: texture_decoder.into(),
+        retry_interval: texture_retry_interval,
+        max_retries: max_texture_retries,
+    };
+    // SAFETY: The objects are created with, and only used on, the context
+    // egui is painting with.
+    let resources =
+        unsafe { GlowResources::new(gl, texture_loading.clone(), on_texture_load_error)? };
+    if let Some(previous) = lock(&RESOURCES).replace(resources) {
+        // SAFETY: Read above.
+        unsafe { previous.destroy(gl) };
+    }
+
+    set_spine_callbacks(fallback_filter, filter_override, texture_loading);
+    Backend::Glow.set();
+
+    Ok(())
+}
+
+pub(super) fn paint_callback(rect: egui::Rect, callback: RendererCallback) -> egui::PaintCallback {
+    egui::PaintCallback {
+        rect,
+        callback: Arc::new(egui_glow::CallbackFn::new(move |_, painter| {
+            let mut resources = lock(&RESOURCES);
+            let Some(resources) = resources.as_mut() else {
+                log::error!("Tried to paint a Spine model before `init_glow_spine_context`");
+                return;
+            };
+            // SAFETY: Read `init_glow_spine_context`.
+            unsafe { resources.paint(painter.gl(), &callback) };
+        })),
+    }
+}
+
+struct GlowResources {
+    program: glow::Program,
+    scene_location: glow::UniformLocation,
+    texture_location: glow::UniformLocation,
+    desaturate_location: glow::UniformLocation,
+    fill_location: glow::UniformLocation,
+    vertex_array: glow::VertexArray,
+    vertex_buffer: glow::Buffer,
+    index_buffer: glow::Buffer,
+    texture_loading: TextureLoading,
+    on_texture_load_error: Option<Box<dyn Fn(TextureError) + Send + Sync + 'static>>,
+}
+
+// SAFETY: The GL objects are only names for objects owned by the GL context,
+// and they are never touched without it: `RESOURCES` is only locked by
+// `init_glow_spine_context` and by the paint callbacks, which are both given
+// the `glow::Context`, and so run on the thread that context is current on.
+// The names are plain integers on native targets, which are already `Send`;
+// this is only needed on the web, where uniform locations are JS objects,
+// and where `wasm32` (without atomics) only ever has a single thread anyway.
+#[cfg(target_arch = "wasm32")]
+unsafe impl Send for GlowResources {}
+
+impl GlowResources {
+    const ATTRIBUTES: [(&str, i32, i32); 4] = [
+        ("a_position", 2, 0),
+        ("a_uv", 2, 8),
+        ("a_color", 4, 16),
+        ("a_dark_color", 4, 32),
+    ];
+
+    unsafe fn new(
+        gl: &glow::Context,
+        texture_loading: TextureLoading,
+        on_texture_load_error: Option<Box<dyn Fn(TextureError) + Send + Sync + 'static>>,
+    ) -> Result<Self, String> {
+        unsafe {
+            let program = create_program(gl)?;
+            let scene_location = gl
+                .get_uniform_location(program, "u_scene")
+                .ok_or("Missing `u_scene` uniform")?;
+            let texture_location = gl
+                .get_uniform_location(program, "u_texture")
+                .ok_or("Missing `u_texture` uniform")?;
+            let desaturate_location = gl
+                .get_uniform_location(program, "u_desaturate")
+                .ok_or("Missing `u_desaturate` uniform")?;
+            let fill_location = gl
+                .get_uniform_location(program, "u_fill")
+                .ok_or("Missing `u_fill` uniform")?;
+
+            let vertex_array = gl.create_vertex_array()?;
+            let vertex_buffer = gl.create_buffer()?;
+            let index_buffer = gl.create_buffer()?;
+
+            gl.bind_vertex_array(Some(vertex_array));
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(vertex_buffer));
+            for (location, (_, size, offset)) in Self::ATTRIBUTES.into_iter().enumerate() {
+                let location = location as u32;
+                let stride = size_of::<Vertex>() as i32;
+                gl.vertex_attrib_pointer_f32(location, size, glow::FLOAT, false, stride, offset);
+                gl.enable_vertex_attrib_array(location);
+            }
+            gl.bind_buffer(glow::ELEMENT_ARRAY_BUFFER, Some(index_buffer));
+            gl.bind_vertex_array(None);
+
+            Ok(Self {
+                program,
+                scene_location,
+                texture_location,
+                desaturate_location,
+                fill_location,
+                vertex_array,
+                vertex_buffer,
+                index_buffer,
+                texture_loading,
+                on_texture_load_error,
+            })
+        }
+    }
+
+    unsafe fn destroy(self, gl: &glow::Context) {
+        unsafe {
+            gl.delete_program(self.program);
+            gl.delete_vertex_array(self.vertex_array);
+            gl.delete_buffer(self.vertex_buffer);
+            gl.delete_buffer(self.index_buffer);
+        }
+    }
+
+    unsafe fn paint(&mut self, gl: &glow::Context, callback: &RendererCallback) {
+        unsafe {
+            for texture in std::mem::take(&mut *lock(&DISPOSED_TEXTURES)) {
+                gl.delete_texture(texture);
+            }
+
+            gl.use_program(Some(self.program));
+            gl.uniform_matrix_4_f32_slice(
+                Some(&self.scene_location),
+                false,
+                &callback.scene_view.to_cols_array(),
+            );
+            gl.uniform_1_i32(Some(&self.texture_location), 0);
+            gl.uniform_1_f32(Some(&self.desaturate_location), callback.desaturate);
+            gl.active_texture(glow::TEXTURE0);
+            gl.bind_vertex_array(Some(self.vertex_array));
+            gl.bind_buffer(glow::ARRAY_BUFFER, Some(self.vertex_buffer));
+
+            match callback.cull_mode {
+                Some(face) => {
+                    gl.enable(glow::CULL_FACE);
+                    gl.front_face(glow::CCW);
+                    gl.cull_face(match face {
+                        Face::Front => glow::FRONT,
+                        Face::Back => glow::BACK,
+                    });
+                }
+                None => gl.disable(glow::CULL_FACE),
+            }
+            gl.enable(glow::BLEND);
+            gl.blend_equation(glow::FUNC_ADD);
+
+            // NOTE: The outline is drawn behind every mesh, so their textures
+            // are polled before drawing any of them.
+            let mut draws = Vec::new();
+            for mesh in callback.meshes.iter() {
+                if mesh.vertices.is_empty() {
+                    continue;
+                }
+                // SAFETY: `GlowTexture` is the registered type in
+                // `set_create_texture_cb`.
+                let Some(spine_texture) = mesh.renderer_object::<GlowTexture>() else {
+                    continue;
+                };
+                if let Some(texture) =
+                    self.poll_texture(gl, spine_texture, mesh.premultiplied_alpha)
+                {
+                    draws.push((mesh, texture));
+                }
+            }
+
+            if let Some(outline) = &callback.outline {
+                // NOTE: The outline is filled with a straight alpha color,
+                // whether the texture alpha is premultiplied or not.
+                let blend_state =
+                    SpineBlendMode::from(BlendMode::Normal).into_spine_blend_state(false);
+                gl.uniform_4_f32_slice(Some(&self.fill_location), &outline.color);
+                for view in &outline.views {
+                    gl.uniform_matrix_4_f32_slice(
+                        Some(&self.scene_location),
+                        false,
+                        &view.to_cols_array(),
+                    );
+                    for (mesh, texture) in &draws {
+                        draw_mesh(gl, mesh, *texture, blend_state);
+                    }
+                }
+                gl.uniform_matrix_4_f32_slice(
+                    Some(&self.scene_location),
+                    false,
+                    &callback.scene_view.to_cols_array(),
+                );
+            }
+            gl.uniform_4_f32_slice(Some(&self.fill_location), &[0.0; 4]);
+
+            for (mesh, texture) in &draws {
+                let blend_state = mesh
+                    .blend_mode
+                    .into_spine_blend_state(mesh.premultiplied_alpha);
+                draw_mesh(gl, mesh, *texture, blend_state);
+            }
+
+            gl.bind_vertex_array(None);
+        }
+    }
+
+    /// Returns the texture of a mesh, once it is loaded.
+    unsafe fn poll_texture(
+        &mut self,
+        gl: &glow::Context,
+        spine_texture: &mut GlowTexture,
+        premultiplied_alpha: bool,
+    ) -> Option<glow::Texture> {
+        unsafe {
+            if let GlowTexture::Failed {
+                path,
+                attempts,
+                last_try,
+                parameters,
+            } = spine_texture
+            {
+                if !self.texture_loading.should_retry(*attempts, *last_try) {
+                    // Meshes with textures that failed to load are not
+                    // drawn.
+                    return None;
+                }
+                let pending = load_pixels(path, &self.texture_loading);
+                *spine_texture = GlowTexture::Loading {
+                    path: std::mem::take(path),
+                    attempts: *attempts,
+                    pending,
+                    parameters: *parameters,
+                };
+            }
+
+            if let GlowTexture::Loading {
+                path,
+                attempts,
+                pending,
+                parameters,
+            } = spine_texture
+            {
+                // NOTE: Meshes are not drawn until their texture is loaded.
+                let pixels = pending.poll()?;
+                match pixels.and_then(|pixels| {
+                    self.create_texture(gl, pixels, premultiplied_alpha, parameters)
+                }) {
+                    Ok(texture) => *spine_texture = GlowTexture::Loaded { texture },
+                    Err(error) => {
+                        if let Some(callback) = &self.on_texture_load_error {
+                            callback(error);
+                        };
+                        *spine_texture = GlowTexture::Failed {
+                            path: std::mem::take(path),
+                            attempts: *attempts + 1,
+                            last_try: self.texture_loading.failed(*attempts + 1),
+                            parameters: *parameters,
+                        };
+                        return None;
+                    }
+                }
+            }
+
+            let GlowTexture::Loaded { texture } = spine_texture else {
+                return None;
+            };
+            Some(*texture)
+        }
+    }
+
+    unsafe fn create_texture(
+        &self,
+        gl: &glow::Context,
+        (width, height, mut pixels): (u32, u32, Vec<u8>),
+        premultiplied_alpha: bool,
+        parameters: &TextureParameters,
+    ) -> Result<glow::Texture, TextureError> {
+        unsafe {
+            // NOTE: Like the `wgpu` renderer does for sRGB surfaces. `egui_glow`
+            // paints with `FRAMEBUFFER_SRGB` disabled, so this only happens if
+            // the integration enables it.
+            let srgb_target = !cfg!(target_arch = "wasm32")
+                && !gl.version().is_embedded
+                && gl.is_enabled(glow::FRAMEBUFFER_SRGB);
+            if srgb_target && premultiplied_alpha {
+                super::premultiply_in_linear_space(&mut pixels);
+            }
+
+            let texture = gl.create_texture().map_err(io::Error::other)?;
+            gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+            gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 1);
+            gl.tex_image_2d(
+                glow::TEXTURE_2D,
+                0,
+                match srgb_target {
+                    true => glow::SRGB8_ALPHA8,
+                    false => glow::RGBA8,
+                } as i32,
+                width as i32,
+                height as i32,
+                0,
+                glow::RGBA,
+                glow::UNSIGNED_BYTE,
+                glow::PixelUnpackData::Slice(Some(&pixels)),
+            );
+
+            let TextureParameters {
+                min_filter,
+                mag_filter,
+                wrap_s,
+                wrap_t,
+                mipmaps,
+            } = *parameters;
+            for (parameter, value) in [
+                (glow::TEXTURE_MIN_FILTER, min_filter),
+                (glow::TEXTURE_MAG_FILTER, mag_filter),
+                (glow::TEXTURE_WRAP_S, wrap_s),
+                (glow::TEXTURE_WRAP_T, wrap_t),
+            ] {
+                gl.tex_parameter_i32(glow::TEXTURE_2D, parameter, value as i32);
+            }
+            if mipmaps {
+                gl.generate_mipmap(glow::TEXTURE_2D);
+            }
+
+            Ok(texture)
+        }
+    }
+}
+
+unsafe fn create_program(gl: &glow::Context) -> Result<glow::Program, String> {
+    let shader_version = ShaderVersion::get(gl);
+    let header = format!(
+        "{}\n#define NEW_SHADER_INTERFACE {}\n",
+        shader_version.version_declaration(),
+        shader_version.is_new_shader_interface() as i32,
+    );
+
+    unsafe {
+        let program = gl.create_program()?;
+        let mut shaders = Vec::new();
+        for (kind, source) in [
+            (glow::VERTEX_SHADER, include_str!("spine.vert")),
+            (glow::FRAGMENT_SHADER, include_str!("spine.frag")),
+        ] {
+            let shader = gl.create_shader(kind)?;
+            gl.shader_source(shader, &format!("{header}{source}"));
+            gl.compile_shader(shader);
+            if !gl.get_shader_compile_status(shader) {
+                return Err(gl.get_shader_info_log(shader));
+            }
+            gl.attach_shader(program, shader);
+            shaders.push(shader);
+        }
+
+        for (location, (name, ..)) in GlowResources::ATTRIBUTES.into_iter().enumerate() {
+            gl.bind_attrib_location(program, location as u32, name);
+        }
+        gl.link_program(program);
+        for shader in shaders {
+            gl.detach_shader(program, shader);
+            gl.delete_shader(shader);
+        }
+        if !gl.get_program_link_status(program) {
+            return Err(gl.get_program_info_log(program));
+        }
+
+        Ok(program)
+    }
+}
+
+fn blend_factor(factor: BlendFactor) -> u32 {
+    match factor {
+        BlendFactor::One => glow::ONE,
+        BlendFactor::OneMinusSrc => glow::ONE_MINUS_SRC_COLOR,
+        BlendFactor::SrcAlpha => glow::SRC_ALPHA,
+        BlendFactor::OneMinusSrcAlpha => glow::ONE_MINUS_SRC_ALPHA,
+        BlendFactor::Dst => glow::DST_COLOR,
+    }
+}
+
+// Texture
+enum GlowTexture {
+    Loading {
+        path: Box<str>,
+        /// How many times the texture failed to load before.
+        attempts: u32,
+        pending: PendingTexture<(u32, u32, Vec<u8>)>,
+        parameters: TextureParameters,
+    },
+    Loaded {
+        texture: glow::Texture,
+    },
+    Failed {
+        path: Box<str>,
+        attempts: u32,
+        last_try: Instant,
+        parameters: TextureParameters,
+    },
+}
+
+#[derive(Clone, Copy)]
+struct TextureParameters {
+    min_filter: u32,
+    mag_filter: u32,
+    wrap_s: u32,
+    wrap_t: u32,
+    /// Whether a mip chain needs to be generated for the texture.
+    mipmaps: bool,
+}
+
+/// Reads and decodes the texture page found at `path` on a worker thread.
+fn load_pixels(
+    path: &str,
+    texture_loading: &TextureLoading,
+) -> PendingTexture<(u32, u32, Vec<u8>)> {
+    let TextureLoading {
+        loader, decoder, ..
+    } = texture_loading.clone();
+    let path = path.to_owned();
+    PendingTexture::spawn(move || super::checked_decode(&*decoder, &loader(&path)?))
+}
+
+fn set_spine_callbacks(
+    fallback_filter: u32,
+    filter_override: Option<u32>,
+    texture_loading: TextureLoading,
+) {
+    rusty_spine::extension::set_create_texture_cb(move |page, path| {
+        // NOTE: Returns the filter, and whether it samples from mipmaps.
+        let convert_filter = |filter| match filter {
+            AtlasFilter::Nearest => (glow::NEAREST, false),
+            AtlasFilter::Linear => (glow::LINEAR, false),
+            AtlasFilter::MipmapNearestNearest => (glow::NEAREST_MIPMAP_NEAREST, true),
+            AtlasFilter::MipmapLinearNearest => (glow::LINEAR_MIPMAP_NEAREST, true),
+            AtlasFilter::MipmapNearestLinear => (glow::NEAREST_MIPMAP_LINEAR, true),
+            AtlasFilter::Mipmap | AtlasFilter::MipmapLinearLinear => {
+                (glow::LINEAR_MIPMAP_LINEAR, true)
+            }
+            filter => {
+                log::warn!("Unsupported texture filter mode {filter:?} in atlas page {path}");
+                (fallback_filter, false)
+            }
+        };
+        let convert_wrap = |wrap| match wrap {
+            AtlasWrap::MirroredRepeat => glow::MIRRORED_REPEAT,
+            AtlasWrap::ClampToEdge => glow::CLAMP_TO_EDGE,
+            AtlasWrap::Repeat => glow::REPEAT,
+            wrap => {
+                log::warn!("Unsupported texture wrap mode {wrap:?} in atlas page {path}");
+                glow::CLAMP_TO_EDGE
+            }
+        };
+        // NOTE: Magnification never samples from mipmaps.
+        let mag_filter = match convert_filter(page.mag_filter()).0 {
+            glow::NEAREST | glow::NEAREST_MIPMAP_NEAREST | glow::NEAREST_MIPMAP_LINEAR => {
+                glow::NEAREST
+            }
+            _ => glow::LINEAR,
+        };
+        let (min_filter, mipmaps) = convert_filter(page.min_filter());
+        let (mag_filter, min_filter) = match (filter_override, mipmaps) {
+            (Some(glow::NEAREST), true) => (glow::NEAREST, glow::NEAREST_MIPMAP_NEAREST),
+            (Some(_), true) => (glow::LINEAR, glow::LINEAR_MIPMAP_LINEAR),
+            (Some(filter), false) => (filter, filter),
+            (None, _) => (mag_filter, min_filter),
+        };
+        page.renderer_object().set(GlowTexture::Loading {
+            path: path.to_owned().into_boxed_str(),
+            attempts: 0,
+            pending: load_pixels(path, &texture_loading),
+            parameters: TextureParameters {
+                min_filter,
+                mag_filter,
+                wrap_s: convert_wrap(page.u_wrap()),
+                wrap_t: convert_wrap(page.v_wrap()),
+                mipmaps,
+            },
+        });
+    });
+
+    rusty_spine::extension::set_dispose_texture_cb(|page| {
+        let mut renderer_object = page.renderer_object();
+        // SAFETY: `GlowTexture` is the registered type in
+        // `set_create_texture_cb`.
+        if let Some(GlowTexture::Loaded { texture }) =
+            unsafe { renderer_object.get::<GlowTexture>() }
+        {
+            lock(&DISPOSED_TEXTURES).push(*texture);
+        }
+        // SAFETY: `GlowTexture` is a rust type that only contains values
+        // allocated with the rust allocator.
+        unsafe { renderer_object.dispose::<GlowTexture>() }
+    });
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Draws `mesh` with `texture`, once the program and vertex array are bound.
+unsafe fn draw_mesh(
+    gl: &glow::Context,
+    mesh: &Mesh<'_>,
+    texture: glow::Texture,
+    blend_state: BlendState,
+) {
+    unsafe {
+        gl.blend_func_separate(
+            blend_factor(blend_state.color.src_factor),
+            blend_factor(blend_state.color.dst_factor),
+            blend_factor(blend_state.alpha.src_factor),
+            blend_factor(blend_state.alpha.dst_factor),
+        );
+
+        gl.bind_texture(glow::TEXTURE_2D, Some(texture));
+        gl.buffer_data_u8_slice(
+            glow::ARRAY_BUFFER,
+            bytemuck::cast_slice(&mesh.vertices),
+            glow::STREAM_DRAW,
+        );
+        gl.buffer_data_u8_slice(
+            glow::ELEMENT_ARRAY_BUFFER,
+            bytemuck::cast_slice(mesh.indices),
+            glow::STREAM_DRAW,
+        );
+        gl.draw_elements(
+            glow::TRIANGLES,
+            mesh.indices.len() as i32,
+            glow::UNSIGNED_SHORT,
+            0,
+        );
+    }
+}
@@ -0,0 +1,91 @@
+//! Minimal reader of KTX2 containers storing block-compressed textures.
+//!
+//! NOTE: Only 2D textures without supercompression are supported, so Basis
+//! Universal textures need to be transcoded beforehand.
+
+use super::TextureError;
+
+const IDENTIFIER: [u8; 12] = [
+    0xAB, b'K', b'T', b'X', b' ', b'2', b'0', 0xBB, b'\r', b'\n', 0x1A, b'\n',
+];
+const HEADER_LEN: usize = 80;
+const LEVEL_INDEX_ENTRY_LEN: usize = 24;
+
+pub(super) struct Ktx2<'a> {
+    /// The `VkFormat` of the texture.
+    pub vk_format: u32,
+    pub width: u32,
+    pub height: u32,
+    /// Data of every mip level, starting from the base level.
+    pub levels: Vec<&'a [u8]>,
+}
+
+impl<'a> Ktx2<'a> {
+    /// Whether `bytes` starts with the KTX2 file identifier.
+    pub fn is_ktx2(bytes: &[u8]) -> bool {
+        bytes.starts_with(&IDENTIFIER)
+    }
+
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, TextureError> {
+        let read_u32 = |offset: usize| {
+            bytes
+                .get(offset..offset + 4)
+                .map(|bytes| u32::from_le_bytes(bytes.try_into().expect("4 bytes")))
+                .ok_or_else(|| invalid("truncated header"))
+        };
+        let read_u64 = |offset: usize| {
+            bytes
+                .get(offset..offset + 8)
+                .map(|bytes| u64::from_le_bytes(bytes.try_into().expect("8 bytes")))
+                .ok_or_else(|| invalid("truncated header"))
+        };
+
+        if !Self::is_ktx2(bytes) {
+            return Err(invalid("missing file identifier"));
+        }
+        let vk_format = read_u32(12)?;
+        let width = read_u32(20)?;
+        let height = read_u32(24)?;
+        let depth = read_u32(28)?;
+        let layer_count = read_u32(32)?;
+        let face_count = read_u32(36)?;
+        // NOTE: Zero means that the mip chain should be generated at load time,
+        // which isn't possible for block-compressed formats.
+        let level_count = read_u32(40)?.max(1);
+        let supercompression_scheme = read_u32(44)?;
+
+        if vk_format == 0 {
+            return Err(invalid("Basis Universal textures are not supported"));
+        }
+        if supercompression_scheme != 0 {
+            return Err(invalid("supercompressed textures are not supported"));
+        }
+        if width == 0 || height == 0 || depth > 1 || layer_count > 1 || face_count != 1 {
+            return Err(invalid("only 2D textures are supported"));
+        }
+
+        let levels = (0..level_count as usize)
+            .map(|level| {
+                let entry = HEADER_LEN + level * LEVEL_INDEX_ENTRY_LEN;
+                let offset = read_u64(entry)?;
+                let len = read_u64(entry + 8)?;
+                usize::try_from(offset)
+                    .ok()
+                    .zip(usize::try_from(len).ok())
+                    .and_then(|(offset, len)| bytes.get(offset..offset.checked_add(len)?))
+                    .ok_or_else(|| invalid("mip level out of bounds"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            vk_format,
+            width,
+            height,
+            levels,
+        })
+    }
+}
+
+fn invalid(reason: &str) -> TextureError {
+    TextureError::Decode(format!("invalid KTX2 texture: {reason}").into())
+}
@@ -0,0 +1,39 @@
+#ifdef GL_ES
+    precision mediump float;
+#endif
+
+#if NEW_SHADER_INTERFACE
+    #define I in
+    out vec4 f_color;
+    #define gl_FragColor f_color
+    #define texture2D texture
+#else
+    #define I varying
+#endif
+
+uniform sampler2D u_texture;
+// Color the meshes are filled with instead, if its alpha isn't zero; used to
+// draw outlines.
+uniform vec4 u_fill;
+// How much the colors are blended towards their luminance.
+uniform float u_desaturate;
+
+I vec2 v_tex_coords;
+I vec4 v_color;
+I vec4 v_dark_color;
+
+void main() {
+    vec4 tex_color = texture2D(u_texture, v_tex_coords);
+
+    if (u_fill.a > 0.0) {
+        gl_FragColor = vec4(u_fill.rgb, u_fill.a * tex_color.a * v_color.a);
+        return;
+    }
+
+    vec3 blended_rgb = ((tex_color.a - 1.0) * v_dark_color.a + 1.0 - tex_color.rgb) * v_dark_color.rgb + tex_color.rgb * v_color.rgb;
+    float blended_a = tex_color.a * v_color.a;
+    float luminance = dot(blended_rgb, vec3(0.2126, 0.7152, 0.0722));
+    vec3 desaturated_rgb = mix(blended_rgb, vec3(luminance), u_desaturate);
+
+    gl_FragColor = vec4(desaturated_rgb, blended_a);
+}
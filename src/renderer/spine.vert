@@ -0,0 +1,29 @@
+#if NEW_SHADER_INTERFACE
+    #define I in
+    #define O out
+#else
+    #define I attribute
+    #define O varying
+#endif
+
+#ifdef GL_ES
+    precision mediump float;
+#endif
+
+uniform mat4 u_scene;
+
+I vec2 a_position;
+I vec2 a_uv;
+I vec4 a_color;
+I vec4 a_dark_color;
+
+O vec2 v_tex_coords;
+O vec4 v_color;
+O vec4 v_dark_color;
+
+void main() {
+    gl_Position = u_scene * vec4(a_position, 0.0, 1.0);
+    v_tex_coords = a_uv;
+    v_color = a_color;
+    v_dark_color = a_dark_color;
+}
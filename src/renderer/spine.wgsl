@@ -14,13 +14,28 @@ struct VertexOutput {
     @location(2) dark_color: vec4<f32>,
 }
 
-@group(0) @binding(0) var<uniform> scene: mat4x4<f32>;
+struct Scene {
+    view: mat4x4<f32>,
+    // Color the meshes are filled with instead, if its alpha isn't zero; used
+    // to draw outlines.
+    fill: vec4<f32>,
+    // How much the colors are blended towards their luminance.
+    desaturate: f32,
+    // Depth the meshes are drawn at, when depth tested.
+    depth: f32,
+}
+
+@group(0) @binding(0) var<uniform> scene: Scene;
+
+// Depth between consecutive meshes, which are drawn as separate instances.
+const DEPTH_STEP: f32 = 1.0 / 65536.0;
 
 @vertex
-fn vs_main(in: VertexInput) -> VertexOutput {
+fn vs_main(in: VertexInput, @builtin(instance_index) instance: u32) -> VertexOutput {
     var out: VertexOutput;
 
-    out.position = scene * vec4<f32>(in.position, 0.0, 1.0);
+    out.position = scene.view * vec4<f32>(in.position, 0.0, 1.0);
+    out.position.z = max(scene.depth - f32(instance) * DEPTH_STEP, 0.0);
     out.tex_coords = in.uv;
     out.color = in.color;
     out.dark_color = in.dark_color;
@@ -37,8 +52,14 @@ fn vs_main(in: VertexInput) -> VertexOutput {
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let tex_color = textureSample(tex, tex_sampler, in.tex_coords);
 
+    if scene.fill.a > 0.0 {
+        return vec4<f32>(scene.fill.rgb, scene.fill.a * tex_color.a * in.color.a);
+    }
+
     let blended_rgb = ((tex_color.a - 1.0) * in.dark_color.a + 1.0 - tex_color.rgb) * in.dark_color.rgb + tex_color.rgb * in.color.rgb;
     let blended_a = tex_color.a * in.color.a;
+    let luminance = dot(blended_rgb, vec3<f32>(0.2126, 0.7152, 0.0722));
+    let desaturated_rgb = mix(blended_rgb, vec3<f32>(luminance), scene.desaturate);
 
-    return vec4<f32>(blended_rgb, blended_a);
+    return vec4<f32>(desaturated_rgb, blended_a);
 }
@@ -1,9 +1,25 @@
-use super::{Face, RendererCallback, Vertex};
-use bevy_color::{LinearRgba, Srgba};
-use egui_wgpu::wgpu::util::{BufferInitDescriptor, DeviceExt, TextureDataOrder};
-use egui_wgpu::{CallbackResources, CallbackTrait, RenderState};
-use rusty_spine::atlas::{AtlasFilter, AtlasWrap};
-use std::num::NonZero;
+use super::ktx2::Ktx2;
+use super::{
+    Backend, BlendComponent as SpineBlendComponent, BlendFactor as SpineBlendFactor,
+    BlendState as SpineBlendState, Meshes, PendingTexture, RendererCallback, SpineBlendMode,
+    TextureDecoder, TextureError, TextureLoader, TextureLoading, Vertex,
+};
+use crate::Face;
+use egui_wgpu::wgpu::util::{DeviceExt, TextureDataOrder};
+use egui_wgpu::{CallbackResources, CallbackTrait, RenderState, ScreenDescriptor};
+use glam::Mat4;
+use rusty_spine::{
+    Atlas, BlendMode,
+    atlas::{AtlasFilter, AtlasWrap},
+};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    num::NonZero,
+    ops::Range,
+    sync::PoisonError,
+    time::{Duration, Instant},
+};
 
 pub(super) use egui_wgpu::wgpu::Face as WgpuFace;
 pub(super) use egui_wgpu::wgpu::*;
@@ -11,7 +27,68 @@ pub(super) use egui_wgpu::wgpu::*;
 type SamplerDesc = SamplerDescriptor<'static>;
 
 pub struct WgpuContextOptions {
-    pub on_texture_load_error: Option<Box<dyn Fn(image::ImageError) + Send + Sync + 'static>>,
+    pub on_texture_load_error: Option<Box<dyn Fn(TextureError) + Send + Sync + 'static>>,
+    /// Reads the bytes of the texture page found at the given path.
+    ///
+    /// Defaults to reading from the filesystem.
+    pub texture_loader: Box<TextureLoader>,
+    /// Decodes the bytes read by `texture_loader`.
+    ///
+    /// Defaults to decoding with the `image` crate, if the `image` feature is
+    /// enabled.
+    ///
+    /// NOTE: KTX2 textures with a BCn, ETC2 or ASTC 4x4 format are uploaded as
+    /// is instead, as long as the device enables the matching
+    /// `TEXTURE_COMPRESSION_*` feature.
+    pub texture_decoder: Box<TextureDecoder>,
+    /// Time to wait before loading again a texture page that failed to load,
+    /// e.g. because it is still being downloaded; doubled after every retry.
+    pub texture_retry_interval: Duration,
+    /// How many times a texture page is loaded again before giving up.
+    pub max_texture_retries: u32,
+    /// Filter used for atlas pages exported with an unsupported filter mode.
+    pub fallback_filter: FilterMode,
+    /// Filter used for every atlas page, regardless of the filter modes they
+    /// were exported with; e.g. `FilterMode::Nearest` for pixel art.
+    pub filter_override: Option<FilterMode>,
+    /// Maximum anisotropy used to sample the atlas pages, sharpening skeletons
+    /// that are scaled down unevenly (e.g. squashed or skewed by an animation);
+    /// `1` disables it.
+    ///
+    /// NOTE: Anisotropic filtering samples from mipmaps, so it only applies to
+    /// pages exported with a mipmap filter (which get a mip chain generated),
+    /// and forces every filter of those pages to `FilterMode::Linear`. It is
+    /// clamped to `16`, and ignored while `filter_override` is set.
+    pub anisotropy: u16,
+    /// Samples per pixel of the render pass egui paints into, which the
+    /// pipelines need to match; e.g. `eframe::NativeOptions::multisampling`.
+    ///
+    /// NOTE: `egui_wgpu` doesn't expose the sample count it was configured
+    /// with, so it can't be read from the `RenderState`.
+    pub msaa_samples: u32,
+    /// Format of the depth buffer of the render pass egui paints into, if it
+    /// has one; e.g. from `eframe::NativeOptions::depth_buffer`.
+    ///
+    /// The skeleton is then depth tested against whatever else is drawn in
+    /// the pass, at the depth set by [`Scene::depth`], with every mesh written
+    /// slightly closer than the ones before it in draw order (down to `0.0`,
+    /// where meshes at the same depth are still drawn in order).
+    ///
+    /// [`Scene::depth`]: crate::Scene::depth
+    pub depth_format: Option<TextureFormat>,
+    /// WGSL source of the shader the skeleton is drawn with, e.g. to add an
+    /// effect to the fragment shader; defaults to
+    /// [`WgpuContextOptions::DEFAULT_SHADER`], which is a good starting point.
+    ///
+    /// NOTE: The shader must have a single vertex and a single fragment entry
+    /// point, and keep the vertex inputs and bind groups of the default one.
+    pub shader: Option<Cow<'static, str>>,
+}
+
+impl WgpuContextOptions {
+    /// WGSL source of the shader used when [`WgpuContextOptions::shader`]
+    /// isn't set.
+    pub const DEFAULT_SHADER: &str = include_str!("spine.wgsl");
 }
 
 impl Default for WgpuContextOptions {
@@ -20,12 +97,47 @@ impl Default for WgpuContextOptions {
             on_texture_load_error: Some(Box::new(|error| {
                 log::error!("Error while loading texture: {error:?}");
             })),
+            texture_loader: Box::new(|path| std::fs::read(path)),
+            texture_decoder: Box::new(super::decode_texture),
+            texture_retry_interval: Duration::from_secs(1),
+            max_texture_retries: 4,
+            fallback_filter: FilterMode::Linear,
+            filter_override: None,
+            anisotropy: 1,
+            msaa_samples: 1,
+            depth_format: None,
+            shader: None,
         }
     }
 }
 
 pub fn init_wgpu_spine_context(render_state: &RenderState, options: WgpuContextOptions) {
-    set_spine_callbacks();
+    let WgpuContextOptions {
+        on_texture_load_error,
+        texture_loader,
+        texture_decoder,
+        texture_retry_interval,
+        max_texture_retries,
+        fallback_filter,
+        filter_override,
+        anisotropy,
+        msaa_samples,
+        depth_format,
+        shader,
+    } = options;
+    let texture_loading = TextureLoading {
+        loader: texture_loader.into(),
+        decoder: texture_decoder.into(),
+        retry_interval: texture_retry_interval,
+        max_retries: max_texture_retries,
+    };
+    set_spine_callbacks(
+        fallback_filter,
+        filter_override,
+        anisotropy,
+        texture_loading.clone(),
+    );
+    Backend::Wgpu.set();
 
     let RenderState {
         device,
@@ -34,18 +146,23 @@ pub fn init_wgpu_spine_context(render_state: &RenderState, options: WgpuContextO
         ..
     } = render_state;
 
-    let shader = device.create_shader_module(include_wgsl!("spine.wgsl"));
+    let shader = device.create_shader_module(ShaderModuleDescriptor {
+        label: Some("Spine Shader"),
+        source: ShaderSource::Wgsl(
+            shader.unwrap_or(Cow::Borrowed(WgpuContextOptions::DEFAULT_SHADER)),
+        ),
+    });
 
     let scene_bind_group_layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
         label: Some("Spine Bind Group Layout"),
         entries: &[BindGroupLayoutEntry {
             binding: 0,
-            visibility: ShaderStages::VERTEX,
+            visibility: ShaderStages::VERTEX | ShaderStages::FRAGMENT,
             ty: BindingType::Buffer {
                 ty: BufferBindingType::Uniform,
-                has_dynamic_offset: false,
-                // PERF(Unavailable): Investigate if this actually matters.
-                min_binding_size: None,
+                has_dynamic_offset: true,
+                // NOTE: The scene buffer always binds exactly one `SceneUniform`.
+                min_binding_size: NonZero::new(size_of::<SceneUniform>() as BufferAddress),
             },
             count: None,
         }],
@@ -79,15 +196,25 @@ pub fn init_wgpu_spine_context(render_state: &RenderState, options: WgpuContextO
         push_constant_ranges: &[],
     });
 
+    let scene = SceneUniforms::new(device, &scene_bind_group_layout);
+
     let resources = WgpuResources {
         device: device.clone(),
         queue: queue.clone(),
-        surface_format: *target_format,
+        surface_target: RenderTarget {
+            format: *target_format,
+            samples: msaa_samples.max(1),
+            depth_format,
+        },
         shader,
         scene_bind_group_layout,
         texture_bind_group_layout,
         pipeline_layout,
-        context_options: options,
+        pipelines: HashMap::new(),
+        scene,
+        meshes: MeshBuffers::new(device),
+        texture_loading,
+        on_texture_load_error,
     };
     render_state
         .renderer
@@ -99,53 +226,335 @@ pub fn init_wgpu_spine_context(render_state: &RenderState, options: WgpuContextO
 struct WgpuResources {
     device: Device,
     queue: Queue,
-    surface_format: TextureFormat,
+    /// Attachments of the render pass egui paints into.
+    surface_target: RenderTarget,
     shader: ShaderModule,
     scene_bind_group_layout: BindGroupLayout,
     texture_bind_group_layout: BindGroupLayout,
     pipeline_layout: PipelineLayout,
-    context_options: WgpuContextOptions,
+    /// Pipelines are only different on their render target, blend state and
+    /// cull mode, so they are shared between all textures.
+    pipelines: HashMap<(RenderTarget, BlendState, Option<WgpuFace>), RenderPipeline>,
+    scene: SceneUniforms,
+    meshes: MeshBuffers,
+    texture_loading: TextureLoading,
+    on_texture_load_error: Option<Box<dyn Fn(TextureError) + Send + Sync + 'static>>,
 }
 
-impl CallbackTrait for RendererCallback {
-    fn paint(
-        &self,
-        _: egui::PaintCallbackInfo,
-        render_pass: &mut RenderPass<'static>,
-        resources: &CallbackResources,
-    ) {
-        let resources: &WgpuResources = resources.get().unwrap();
-        let WgpuResources {
-            device,
-            queue,
-            scene_bind_group_layout,
-            context_options,
-            ..
-        } = &resources;
+/// Attachments of a render pass, which the pipelines drawing in it need to
+/// match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct RenderTarget {
+    format: TextureFormat,
+    samples: u32,
+    depth_format: Option<TextureFormat>,
+}
 
-        let scene_buffer = device.create_buffer_init(&BufferInitDescriptor {
+/// The `Scene` uniform of `spine.wgsl`.
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct SceneUniform {
+    view: Mat4,
+    fill: [f32; 4],
+    desaturate: f32,
+    depth: f32,
+    _padding: [f32; 2],
+}
+
+impl SceneUniform {
+    fn new(view: Mat4, fill: [f32; 4], desaturate: f32, depth: f32) -> Self {
+        Self {
+            view,
+            fill,
+            desaturate,
+            depth,
+            _padding: [0.0; 2],
+        }
+    }
+}
+
+/// Uniform buffer shared by all the callbacks of a frame, each one binding its
+/// scene through a dynamic offset.
+struct SceneUniforms {
+    buffer: Buffer,
+    bind_group: BindGroup,
+    /// Scenes written during the current frame.
+    views: Vec<SceneUniform>,
+    /// Distance between consecutive scene views, honoring the device's uniform
+    /// offset alignment.
+    stride: BufferAddress,
+}
+
+impl SceneUniforms {
+    const INITIAL_CAPACITY: BufferAddress = 4;
+
+    fn new(device: &Device, layout: &BindGroupLayout) -> Self {
+        let alignment = BufferAddress::from(device.limits().min_uniform_buffer_offset_alignment);
+        let stride = (size_of::<SceneUniform>() as BufferAddress).next_multiple_of(alignment);
+        let (buffer, bind_group) =
+            Self::create_buffer(device, layout, stride * Self::INITIAL_CAPACITY);
+
+        Self {
+            buffer,
+            bind_group,
+            views: Vec::new(),
+            stride,
+        }
+    }
+
+    fn create_buffer(
+        device: &Device,
+        layout: &BindGroupLayout,
+        size: BufferAddress,
+    ) -> (Buffer, BindGroup) {
+        let buffer = device.create_buffer(&BufferDescriptor {
             label: Some("Spine Scene Buffer"),
-            contents: bytemuck::bytes_of(&self.scene_view),
+            size,
             usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
         });
-        let scene_bind_group = device.create_bind_group(&BindGroupDescriptor {
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
             label: Some("Spine Scene Bind Group"),
-            layout: &scene_bind_group_layout,
+            layout,
             entries: &[BindGroupEntry {
                 binding: 0,
-                resource: scene_buffer.as_entire_binding(),
+                resource: BindingResource::Buffer(BufferBinding {
+                    buffer: &buffer,
+                    offset: 0,
+                    size: NonZero::new(size_of::<SceneUniform>() as BufferAddress),
+                }),
             }],
         });
-        render_pass.set_bind_group(0, &scene_bind_group, &[]);
 
-        for mesh in self.meshes.iter() {
+        (buffer, bind_group)
+    }
+
+    /// Writes `view` to the buffer, returning its dynamic offset.
+    fn push(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        layout: &BindGroupLayout,
+        view: SceneUniform,
+    ) -> DynamicOffset {
+        let offset = self.stride * self.views.len() as BufferAddress;
+        self.views.push(view);
+
+        if offset + self.stride > self.buffer.size() {
+            let size = (self.buffer.size() * 2).max(offset + self.stride);
+            (self.buffer, self.bind_group) = Self::create_buffer(device, layout, size);
+            // NOTE: The views written before growing were written into the
+            // old buffer.
+            for (index, view) in self.views.iter().enumerate() {
+                let offset = self.stride * index as BufferAddress;
+                queue.write_buffer(&self.buffer, offset, bytemuck::bytes_of(view));
+            }
+        } else {
+            queue.write_buffer(&self.buffer, offset, bytemuck::bytes_of(&view));
+        }
+
+        offset as DynamicOffset
+    }
+}
+
+impl CallbackTrait for RendererCallback {
+    fn prepare(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        _: &ScreenDescriptor,
+        _: &mut CommandEncoder,
+        resources: &mut CallbackResources,
+    ) -> Vec<CommandBuffer> {
+        let resources: &mut WgpuResources = resources.get_mut().unwrap();
+        let prepared =
+            resources.push_callback(device, queue, self, resources.surface_target, false);
+        *self.prepared.lock().unwrap_or_else(PoisonError::into_inner) = prepared;
+
+        Vec::new()
+    }
+
+    fn finish_prepare(
+        &self,
+        device: &Device,
+        queue: &Queue,
+        _: &mut CommandEncoder,
+        resources: &mut CallbackResources,
+    ) -> Vec<CommandBuffer> {
+        // NOTE: All callbacks are prepared before any of them is finished, so
+        // the first one uploads the meshes of every callback at once, and the
+        // scene views can already be forgotten for the next frame.
+        let resources: &mut WgpuResources = resources.get_mut().unwrap();
+        resources.meshes.upload(device, queue);
+        resources.scene.views.clear();
+
+        Vec::new()
+    }
+
+    fn paint(
+        &self,
+        _: egui::PaintCallbackInfo,
+        render_pass: &mut RenderPass<'static>,
+        resources: &CallbackResources,
+    ) {
+        let resources: &WgpuResources = resources.get().unwrap();
+        let prepared = self.prepared.lock().unwrap_or_else(PoisonError::into_inner);
+        resources.draw_callback(render_pass, &prepared);
+    }
+}
+
+/// A callback prepared to be drawn on `paint`.
+#[derive(Default)]
+pub(super) struct PreparedCallback {
+    /// Offsets of the scenes the outline is drawn with, one for every copy of
+    /// the skeleton.
+    outline_offsets: Vec<DynamicOffset>,
+    outline_draws: Vec<WgpuDraw>,
+    scene_offset: DynamicOffset,
+    draws: Vec<WgpuDraw>,
+}
+
+/// A mesh prepared to be drawn on `paint`.
+#[derive(Clone)]
+pub(super) struct WgpuDraw {
+    pipeline: RenderPipeline,
+    texture_bind_group: BindGroup,
+    /// Range of the mesh inside [`MeshBuffers::vertex_buffer`].
+    vertices: Range<BufferAddress>,
+    /// Range of the mesh inside [`MeshBuffers::index_buffer`].
+    indices: Range<BufferAddress>,
+    index_count: u32,
+    /// Offsets the depth of the mesh by its position in draw order; see
+    /// `spine.wgsl`.
+    instance: u32,
+}
+
+/// Vertex and index buffers shared by all the callbacks of a frame.
+struct MeshBuffers {
+    vertex_buffer: Buffer,
+    index_buffer: Buffer,
+    /// Vertices pushed during the current frame, until they are uploaded.
+    vertices: Vec<Vertex>,
+    /// Indices pushed during the current frame, until they are uploaded.
+    indices: Vec<u16>,
+}
+
+impl MeshBuffers {
+    const INITIAL_SIZE: BufferAddress = 1 << 16;
+
+    fn new(device: &Device) -> Self {
+        Self {
+            vertex_buffer: Self::create_vertex_buffer(device, Self::INITIAL_SIZE),
+            index_buffer: Self::create_index_buffer(device, Self::INITIAL_SIZE),
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        }
+    }
+
+    fn create_vertex_buffer(device: &Device, size: BufferAddress) -> Buffer {
+        device.create_buffer(&BufferDescriptor {
+            label: Some("Spine Vertex Buffer"),
+            size,
+            usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    fn create_index_buffer(device: &Device, size: BufferAddress) -> Buffer {
+        device.create_buffer(&BufferDescriptor {
+            label: Some("Spine Index Buffer"),
+            size,
+            usage: BufferUsages::INDEX | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Queues a mesh to be uploaded, returning the ranges it will occupy in
+    /// the vertex and index buffers.
+    fn push(
+        &mut self,
+        vertices: &[Vertex],
+        indices: &[u16],
+    ) -> (Range<BufferAddress>, Range<BufferAddress>) {
+        let vertices_start = size_of_val(self.vertices.as_slice()) as BufferAddress;
+        let indices_start = size_of_val(self.indices.as_slice()) as BufferAddress;
+        self.vertices.extend_from_slice(vertices);
+        self.indices.extend_from_slice(indices);
+
+        (
+            vertices_start..vertices_start + size_of_val(vertices) as BufferAddress,
+            indices_start..indices_start + size_of_val(indices) as BufferAddress,
+        )
+    }
+
+    /// Uploads the meshes pushed since the last upload, growing the buffers if
+    /// needed.
+    fn upload(&mut self, device: &Device, queue: &Queue) {
+        if self.vertices.is_empty() {
+            return;
+        }
+
+        // NOTE: We don't need to do this with `self.vertices`, because
+        // `size_of::<Vertex>` is divisible by `COPY_BUFFER_ALIGNMENT`.
+        if !self.indices.len().is_multiple_of(2) {
+            self.indices.push(0);
+        }
+
+        let vertices: &[u8] = bytemuck::cast_slice(&self.vertices);
+        let indices: &[u8] = bytemuck::cast_slice(&self.indices);
+        if vertices.len() as BufferAddress > self.vertex_buffer.size() {
+            let size = (vertices.len() as BufferAddress).next_power_of_two();
+            self.vertex_buffer = Self::create_vertex_buffer(device, size);
+        }
+        if indices.len() as BufferAddress > self.index_buffer.size() {
+            let size = (indices.len() as BufferAddress).next_power_of_two();
+            self.index_buffer = Self::create_index_buffer(device, size);
+        }
+        queue.write_buffer(&self.vertex_buffer, 0, vertices);
+        queue.write_buffer(&self.index_buffer, 0, indices);
+
+        self.vertices.clear();
+        self.indices.clear();
+    }
+}
+
+impl WgpuResources {
+    /// Returns the cached pipeline drawing into `target` with `blend_state`
+    /// and `cull_mode`, creating it if needed.
+    fn pipeline(
+        &mut self,
+        target: RenderTarget,
+        blend_state: BlendState,
+        cull_mode: Option<WgpuFace>,
+    ) -> RenderPipeline {
+        let key = (target, blend_state, cull_mode);
+        if let Some(pipeline) = self.pipelines.get(&key) {
+            return pipeline.clone();
+        }
+
+        let pipeline = self.create_render_pipeline(target, blend_state, cull_mode);
+        self.pipelines.insert(key, pipeline.clone());
+        pipeline
+    }
+
+    /// Pushes the meshes whose texture is loaded into the mesh buffers,
+    /// returning how to draw them into `target`.
+    ///
+    /// If `wait` is set, textures still loading are waited for instead of
+    /// skipping their meshes.
+    fn push_draws(
+        &mut self,
+        meshes: &Meshes,
+        target: RenderTarget,
+        cull_mode: Option<WgpuFace>,
+        wait: bool,
+    ) -> Vec<WgpuDraw> {
+        let mut draws = Vec::new();
+        for mesh in meshes.iter() {
             if mesh.vertices.is_empty() {
                 continue;
             }
 
-            let blend_state = mesh.blend_mode.into_blend_state(mesh.premultiplied_alpha);
-            let cull_mode = self.cull_mode.map(Face::into_wgpu_face);
-
             // SAFETY: `WgpuTexture` is the registered type in
             // `set_create_texture_cb`.
             let spine_texture = unsafe { mesh.renderer_object::<WgpuTexture>() };
@@ -153,93 +562,131 @@ impl CallbackTrait for RendererCallback {
                 continue;
             };
 
-            let vertex_buffer_size = (mesh.vertices.len() * size_of::<Vertex>()) as BufferAddress;
-
-            let indices_len = mesh.indices.len();
-            // NOTE: We don't need to do this with `mesh.vertices`, because
-            // `size_of::<Vertex>` is divisible by `COPY_BUFFER_ALIGNMENT`.
-            let padded_index_buffer_size = {
-                let len = if indices_len % COPY_BUFFER_ALIGNMENT as usize == 0 {
-                    indices_len
-                } else {
-                    indices_len + 1
-                };
-                (len * size_of::<u16>()) as BufferAddress
-            };
-
-            if let WgpuTexture::Loading { path, sampler_desc } = spine_texture {
-                let vertex_buffer = device.create_buffer(&BufferDescriptor {
-                    label: Some("Spine Vertex Buffer"),
-                    size: vertex_buffer_size,
-                    usage: BufferUsages::VERTEX | BufferUsages::COPY_DST,
-                    mapped_at_creation: false,
-                });
-
-                let index_buffer = device.create_buffer(&BufferDescriptor {
-                    label: Some("Spine Index Buffer"),
-                    size: padded_index_buffer_size,
-                    usage: BufferUsages::INDEX | BufferUsages::COPY_DST,
-                    mapped_at_creation: false,
-                });
-
-                let pipeline = resources.create_render_pipeline(blend_state, cull_mode);
-
-                match resources.create_texture_bind_group(
-                    path,
-                    mesh.premultiplied_alpha,
-                    sampler_desc,
-                ) {
-                    Ok(texture_bind_group) => {
-                        *spine_texture = WgpuTexture::Loaded {
-                            pipeline,
-                            vertex_buffer,
-                            index_buffer,
-                            texture_bind_group,
-                        };
-                    }
-                    Err(error) => {
-                        if let Some(callback) = &context_options.on_texture_load_error {
-                            callback(error);
-                        };
-                        continue;
-                    }
-                };
+            let Some(texture_bind_group) = spine_texture
+                .poll(self, mesh.premultiplied_alpha, wait)
+                .cloned()
+            else {
+                // NOTE: Meshes are not drawn until their texture is loaded, and
+                // meshes with textures that failed to load are not drawn.
+                continue;
             };
 
-            let WgpuTexture::Loaded {
-                pipeline,
-                vertex_buffer,
-                index_buffer,
+            let blend_state = mesh.blend_mode.into_blend_state(mesh.premultiplied_alpha);
+            let (vertices, indices) = self.meshes.push(&mesh.vertices, mesh.indices);
+            draws.push(WgpuDraw {
+                pipeline: self.pipeline(target, blend_state, cull_mode),
                 texture_bind_group,
-            } = &spine_texture
-            else {
-                unreachable!()
+                vertices,
+                indices,
+                index_count: mesh.indices.len() as u32,
+                // NOTE: Only used with a depth buffer, to not rely on instance
+                // offsets otherwise. The first instance is left for the
+                // outline, which is drawn behind every mesh.
+                instance: match target.depth_format {
+                    Some(_) => draws.len() as u32 + 1,
+                    None => 0,
+                },
+            });
+        }
+        draws
+    }
+
+    /// Pushes the scenes and meshes of `callback`, returning how to draw it
+    /// into `target`; see [`WgpuResources::push_draws`].
+    fn push_callback(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        callback: &RendererCallback,
+        target: RenderTarget,
+        wait: bool,
+    ) -> PreparedCallback {
+        let layout = &self.scene_bind_group_layout;
+        let scene_offset = self.scene.push(
+            device,
+            queue,
+            layout,
+            SceneUniform::new(
+                callback.scene_view,
+                [0.0; 4],
+                callback.desaturate,
+                callback.depth,
+            ),
+        );
+        let cull_mode = callback.cull_mode.map(Face::into_wgpu_face);
+        let draws = self.push_draws(&callback.meshes, target, cull_mode, wait);
+
+        let Some(outline) = &callback.outline else {
+            return PreparedCallback {
+                scene_offset,
+                draws,
+                ..Default::default()
             };
+        };
+        let layout = &self.scene_bind_group_layout;
+        let outline_offsets = outline
+            .views
+            .iter()
+            .map(|&view| {
+                let scene = SceneUniform::new(view, outline.color, 0.0, callback.depth);
+                self.scene.push(device, queue, layout, scene)
+            })
+            .collect();
+        // NOTE: The outline is filled with a straight alpha color, whether the
+        // texture alpha is premultiplied or not.
+        let blend_state = SpineBlendMode::from(BlendMode::Normal).into_blend_state(false);
+        let pipeline = self.pipeline(target, blend_state, cull_mode);
+        let outline_draws = draws
+            .iter()
+            .map(|draw| WgpuDraw {
+                pipeline: pipeline.clone(),
+                instance: 0,
+                ..draw.clone()
+            })
+            .collect();
 
-            if let Some(mut view) =
-                queue.write_buffer_with(vertex_buffer, 0, nonzero(vertex_buffer_size))
-            {
-                view.copy_from_slice(bytemuck::cast_slice(&mesh.vertices));
-            }
-            if let Some(mut view) =
-                queue.write_buffer_with(index_buffer, 0, nonzero(padded_index_buffer_size))
-            {
-                view[..indices_len * size_of::<u16>()]
-                    .copy_from_slice(bytemuck::cast_slice(&mesh.indices));
-            }
+        PreparedCallback {
+            outline_offsets,
+            outline_draws,
+            scene_offset,
+            draws,
+        }
+    }
 
-            render_pass.set_pipeline(pipeline);
-            render_pass.set_bind_group(1, texture_bind_group, &[]);
-            render_pass.set_vertex_buffer(0, vertex_buffer.slice(..));
-            render_pass.set_index_buffer(index_buffer.slice(..), IndexFormat::Uint16);
-            render_pass.draw_indexed(0..indices_len as u32, 0, 0..1);
+    /// Draws `prepared`, once its meshes and scenes are uploaded.
+    fn draw_callback(&self, render_pass: &mut RenderPass<'_>, prepared: &PreparedCallback) {
+        for &scene_offset in &prepared.outline_offsets {
+            self.draw(render_pass, scene_offset, &prepared.outline_draws);
+        }
+        self.draw(render_pass, prepared.scene_offset, &prepared.draws);
+    }
+
+    /// Draws `draws`, once their meshes and the scene view at `scene_offset`
+    /// are uploaded.
+    fn draw(
+        &self,
+        render_pass: &mut RenderPass<'_>,
+        scene_offset: DynamicOffset,
+        draws: &[WgpuDraw],
+    ) {
+        let WgpuResources { scene, meshes, .. } = self;
+
+        render_pass.set_bind_group(0, &scene.bind_group, &[scene_offset]);
+        for draw in draws {
+            render_pass.set_pipeline(&draw.pipeline);
+            render_pass.set_bind_group(1, &draw.texture_bind_group, &[]);
+            render_pass.set_vertex_buffer(0, meshes.vertex_buffer.slice(draw.vertices.clone()));
+            render_pass.set_index_buffer(
+                meshes.index_buffer.slice(draw.indices.clone()),
+                IndexFormat::Uint16,
+            );
+            render_pass.draw_indexed(0..draw.index_count, 0, draw.instance..draw.instance + 1);
         }
     }
-}
 
-impl WgpuResources {
     fn create_render_pipeline(
         &self,
+        target: RenderTarget,
         blend_state: BlendState,
         cull_mode: Option<WgpuFace>,
     ) -> RenderPipeline {
@@ -257,7 +704,7 @@ impl WgpuResources {
                     module: &self.shader,
                     entry_point: None,
                     targets: &[Some(ColorTargetState {
-                        format: self.surface_format,
+                        format: target.format,
                         blend: Some(blend_state),
                         write_mask: ColorWrites::ALL,
                     })],
@@ -269,8 +716,17 @@ impl WgpuResources {
                     cull_mode,
                     ..Default::default()
                 },
-                multisample: MultisampleState::default(),
-                depth_stencil: None,
+                multisample: MultisampleState {
+                    count: target.samples,
+                    ..Default::default()
+                },
+                depth_stencil: target.depth_format.map(|format| DepthStencilState {
+                    format,
+                    depth_write_enabled: true,
+                    depth_compare: CompareFunction::LessEqual,
+                    stencil: StencilState::default(),
+                    bias: DepthBiasState::default(),
+                }),
                 multiview: None,
                 cache: None,
             })
@@ -278,72 +734,17 @@ impl WgpuResources {
 
     fn create_texture_bind_group(
         &self,
-        path: &str,
+        data: TextureData,
         premultiplied_alpha: bool,
+        mipmaps: bool,
         sampler_desc: &SamplerDesc,
-    ) -> image::ImageResult<BindGroup> {
-        let bytes = std::fs::read(&path)?;
-        let image = image::load_from_memory(&bytes)?;
-
-        let pixels = image.to_rgba8();
-        let (width, height) = pixels.dimensions();
-        let mut pixels = pixels.into_vec();
-
-        // TODO(Unavailable): Rewrite with `epaint`.
-        if self.surface_format.is_srgb() && premultiplied_alpha {
-            for i in 0..(pixels.len() / 4) {
-                let srgba = Srgba::rgba_u8(
-                    pixels[i * 4],
-                    pixels[i * 4 + 1],
-                    pixels[i * 4 + 2],
-                    pixels[i * 4 + 3],
-                );
-                let srgba = if srgba.alpha != 0. {
-                    Srgba::new(
-                        srgba.red / srgba.alpha,
-                        srgba.green / srgba.alpha,
-                        srgba.blue / srgba.alpha,
-                        srgba.alpha,
-                    )
-                } else {
-                    Srgba::new(0., 0., 0., 0.)
-                };
-                let mut lrgba = LinearRgba::from(srgba);
-                lrgba.red *= lrgba.alpha;
-                lrgba.green *= lrgba.alpha;
-                lrgba.blue *= lrgba.alpha;
-                let srgba = Srgba::from(lrgba);
-                pixels[i * 4] = (srgba.red * 255.) as u8;
-                pixels[i * 4 + 1] = (srgba.green * 255.) as u8;
-                pixels[i * 4 + 2] = (srgba.blue * 255.) as u8;
-                pixels[i * 4 + 3] = (srgba.alpha * 255.) as u8;
+    ) -> Result<BindGroup, TextureError> {
+        let texture = match data {
+            TextureData::Ktx2(bytes) => self.create_compressed_texture(Ktx2::parse(&bytes)?)?,
+            TextureData::Rgba(width, height, pixels) => {
+                self.create_rgba_texture(width, height, pixels, premultiplied_alpha, mipmaps)
             }
-        }
-
-        let format = if self.surface_format.is_srgb() {
-            TextureFormat::Rgba8UnormSrgb
-        } else {
-            TextureFormat::Rgba8Unorm
         };
-        let texture = self.device.create_texture_with_data(
-            &self.queue,
-            &TextureDescriptor {
-                label: Some("Spine Texture"),
-                size: Extent3d {
-                    width,
-                    height,
-                    depth_or_array_layers: 1,
-                },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: TextureDimension::D2,
-                format,
-                usage: TextureUsages::TEXTURE_BINDING,
-                view_formats: &[],
-            },
-            TextureDataOrder::default(),
-            &pixels,
-        );
 
         let view = texture.create_view(&TextureViewDescriptor {
             label: Some("Spine Texture View"),
@@ -367,54 +768,609 @@ impl WgpuResources {
 
         Ok(bind_group)
     }
+
+    /// Uploads a block-compressed texture as is, including its mip levels.
+    ///
+    /// NOTE: Unlike [`Self::create_rgba_texture`], the pixels of premultiplied
+    /// alpha textures are not converted to linear space, since that would
+    /// require decompressing them, and whether they are sampled as sRGB is
+    /// up to their `VkFormat` rather than the surface format.
+    fn create_compressed_texture(&self, ktx2: Ktx2<'_>) -> Result<Texture, TextureError> {
+        let unsupported = |reason: String| TextureError::Decode(reason.into());
+
+        let format = compressed_format(ktx2.vk_format)
+            .ok_or_else(|| unsupported(format!("unsupported VkFormat {}", ktx2.vk_format)))?;
+        let required_features = format.required_features();
+        if !self.device.features().contains(required_features) {
+            return Err(unsupported(format!(
+                "{format:?} textures require the {required_features:?} device features"
+            )));
+        }
+
+        let (block_width, block_height) = format.block_dimensions();
+        if !ktx2.width.is_multiple_of(block_width) || !ktx2.height.is_multiple_of(block_height) {
+            return Err(unsupported(format!(
+                "{}x{} is not a multiple of the {block_width}x{block_height} block size",
+                ktx2.width, ktx2.height
+            )));
+        }
+        let block_size = format.block_copy_size(None).expect("color format");
+        for (level, data) in ktx2.levels.iter().enumerate() {
+            let blocks_x = (ktx2.width >> level).max(1).div_ceil(block_width);
+            let blocks_y = (ktx2.height >> level).max(1).div_ceil(block_height);
+            let expected_len = (blocks_x * blocks_y * block_size) as usize;
+            if data.len() != expected_len {
+                return Err(unsupported(format!(
+                    "mip level {level} has {} bytes, expected {expected_len}",
+                    data.len()
+                )));
+            }
+        }
+
+        let texture = self.device.create_texture_with_data(
+            &self.queue,
+            &TextureDescriptor {
+                label: Some("Spine Texture"),
+                size: Extent3d {
+                    width: ktx2.width,
+                    height: ktx2.height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: ktx2.levels.len() as u32,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format,
+                usage: TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            TextureDataOrder::LayerMajor,
+            &ktx2.levels.concat(),
+        );
+
+        Ok(texture)
+    }
+
+    fn create_rgba_texture(
+        &self,
+        width: u32,
+        height: u32,
+        mut pixels: Vec<u8>,
+        premultiplied_alpha: bool,
+        mipmaps: bool,
+    ) -> Texture {
+        if self.surface_target.format.is_srgb() && premultiplied_alpha {
+            super::premultiply_in_linear_space(&mut pixels);
+        }
+
+        let mip_level_count = match mipmaps {
+            true => width.max(height).max(1).ilog2() + 1,
+            false => 1,
+        };
+        let mut data = pixels;
+        let mut mip_start = 0;
+        for level in 1..mip_level_count {
+            let mip = downsample(
+                &data[mip_start..],
+                width >> (level - 1),
+                height >> (level - 1),
+            );
+            mip_start = data.len();
+            data.extend(mip);
+        }
+
+        let format = if self.surface_target.format.is_srgb() {
+            TextureFormat::Rgba8UnormSrgb
+        } else {
+            TextureFormat::Rgba8Unorm
+        };
+        self.device.create_texture_with_data(
+            &self.queue,
+            &TextureDescriptor {
+                label: Some("Spine Texture"),
+                size: Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format,
+                usage: TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+            TextureDataOrder::LayerMajor,
+            &data,
+        )
+    }
+}
+
+/// Converts the `VkFormat` of a KTX2 texture into its block-compressed format.
+///
+/// NOTE: wgpu has no BC1 format without alpha, so the RGB ones are read as
+/// RGBA with opaque blocks, which is how they are encoded.
+fn compressed_format(vk_format: u32) -> Option<TextureFormat> {
+    Some(match vk_format {
+        131 | 133 => TextureFormat::Bc1RgbaUnorm,
+        132 | 134 => TextureFormat::Bc1RgbaUnormSrgb,
+        135 => TextureFormat::Bc2RgbaUnorm,
+        136 => TextureFormat::Bc2RgbaUnormSrgb,
+        137 => TextureFormat::Bc3RgbaUnorm,
+        138 => TextureFormat::Bc3RgbaUnormSrgb,
+        145 => TextureFormat::Bc7RgbaUnorm,
+        146 => TextureFormat::Bc7RgbaUnormSrgb,
+        147 => TextureFormat::Etc2Rgb8Unorm,
+        148 => TextureFormat::Etc2Rgb8UnormSrgb,
+        149 => TextureFormat::Etc2Rgb8A1Unorm,
+        150 => TextureFormat::Etc2Rgb8A1UnormSrgb,
+        151 => TextureFormat::Etc2Rgba8Unorm,
+        152 => TextureFormat::Etc2Rgba8UnormSrgb,
+        157 => TextureFormat::Astc {
+            block: AstcBlock::B4x4,
+            channel: AstcChannel::Unorm,
+        },
+        158 => TextureFormat::Astc {
+            block: AstcBlock::B4x4,
+            channel: AstcChannel::UnormSrgb,
+        },
+        _ => return None,
+    })
+}
+
+/// Halves an RGBA8 image with a box filter; sizes are clamped to 1.
+fn downsample(pixels: &[u8], width: u32, height: u32) -> Vec<u8> {
+    let (width, height) = (width.max(1), height.max(1));
+    let (mip_width, mip_height) = ((width / 2).max(1), (height / 2).max(1));
+    let pixel = |x: u32, y: u32| {
+        let index = (y.min(height - 1) * width + x.min(width - 1)) as usize * 4;
+        &pixels[index..index + 4]
+    };
+
+    let mut mip = Vec::with_capacity(mip_width as usize * mip_height as usize * 4);
+    for y in 0..mip_height {
+        for x in 0..mip_width {
+            let samples = [
+                pixel(x * 2, y * 2),
+                pixel(x * 2 + 1, y * 2),
+                pixel(x * 2, y * 2 + 1),
+                pixel(x * 2 + 1, y * 2 + 1),
+            ];
+            for channel in 0..4 {
+                let sum: u32 = samples.iter().map(|pixel| u32::from(pixel[channel])).sum();
+                mip.push(((sum + 2) / 4) as u8);
+            }
+        }
+    }
+
+    mip
+}
+
+impl Face {
+    pub fn into_wgpu_face(self) -> WgpuFace {
+        match self {
+            Face::Front => WgpuFace::Front,
+            Face::Back => WgpuFace::Back,
+        }
+    }
+}
+
+impl SpineBlendMode {
+    /// The `wgpu` blend state used by the built-in renderer.
+    pub fn into_blend_state(self, premultiplied_alpha: bool) -> BlendState {
+        self.into_spine_blend_state(premultiplied_alpha).into_wgpu()
+    }
+}
+
+impl SpineBlendState {
+    pub fn into_wgpu(self) -> BlendState {
+        let component = |component: SpineBlendComponent| BlendComponent {
+            src_factor: component.src_factor.into_wgpu(),
+            dst_factor: component.dst_factor.into_wgpu(),
+            operation: BlendOperation::Add,
+        };
+        BlendState {
+            color: component(self.color),
+            alpha: component(self.alpha),
+        }
+    }
+}
+
+impl SpineBlendFactor {
+    pub fn into_wgpu(self) -> BlendFactor {
+        match self {
+            SpineBlendFactor::One => BlendFactor::One,
+            SpineBlendFactor::OneMinusSrc => BlendFactor::OneMinusSrc,
+            SpineBlendFactor::SrcAlpha => BlendFactor::SrcAlpha,
+            SpineBlendFactor::OneMinusSrcAlpha => BlendFactor::OneMinusSrcAlpha,
+            SpineBlendFactor::Dst => BlendFactor::Dst,
+        }
+    }
 }
 
 // Texture
 enum WgpuTexture {
     Loading {
         path: Box<str>,
+        /// How many times the texture failed to load before.
+        attempts: u32,
+        pending: PendingTexture<TextureData>,
+        /// Whether a mip chain needs to be generated for the texture.
+        mipmaps: bool,
         sampler_desc: SamplerDesc,
     },
     Loaded {
-        pipeline: RenderPipeline,
-        vertex_buffer: Buffer,
-        index_buffer: Buffer,
         texture_bind_group: BindGroup,
     },
+    Failed {
+        path: Box<str>,
+        attempts: u32,
+        last_try: Instant,
+        mipmaps: bool,
+        sampler_desc: SamplerDesc,
+    },
 }
 
-fn set_spine_callbacks() {
-    rusty_spine::extension::set_create_texture_cb(move |page, path| {
-        fn convert_filter(filter: AtlasFilter) -> FilterMode {
-            match filter {
-                AtlasFilter::Nearest => FilterMode::Nearest,
-                AtlasFilter::Linear => FilterMode::Linear,
-                // TODO(Unavailable): mips
-                filter => {
-                    log::warn!("Unsupported texture filter mode: {filter:?}");
-                    FilterMode::Linear
-                }
+impl WgpuTexture {
+    /// Advances the loading of the texture, and returns its bind group once it
+    /// is loaded.
+    ///
+    /// If `wait` is set, blocks until a pending load finishes instead of
+    /// checking on it again later.
+    fn poll(
+        &mut self,
+        resources: &WgpuResources,
+        premultiplied_alpha: bool,
+        wait: bool,
+    ) -> Option<&BindGroup> {
+        if let WgpuTexture::Failed {
+            path,
+            attempts,
+            last_try,
+            mipmaps,
+            sampler_desc,
+        } = self
+        {
+            if !resources.texture_loading.should_retry(*attempts, *last_try) {
+                return None;
             }
+            let pending = TextureData::load(path, &resources.texture_loading);
+            *self = WgpuTexture::Loading {
+                path: std::mem::take(path),
+                attempts: *attempts,
+                pending,
+                mipmaps: *mipmaps,
+                sampler_desc: sampler_desc.clone(),
+            };
         }
-        fn convert_wrap(wrap: AtlasWrap) -> AddressMode {
-            match wrap {
-                AtlasWrap::MirroredRepeat => AddressMode::MirrorRepeat,
-                AtlasWrap::ClampToEdge => AddressMode::ClampToEdge,
-                AtlasWrap::Repeat => AddressMode::Repeat,
-                wrap => {
-                    log::warn!("Unsupported texture wrap mode: {wrap:?}");
-                    AddressMode::ClampToEdge
+
+        if let WgpuTexture::Loading {
+            path,
+            attempts,
+            pending,
+            mipmaps,
+            sampler_desc,
+        } = self
+        {
+            let data = match wait {
+                true => pending.wait(),
+                false => pending.poll()?,
+            };
+            match data.and_then(|data| {
+                resources.create_texture_bind_group(
+                    data,
+                    premultiplied_alpha,
+                    *mipmaps,
+                    sampler_desc,
+                )
+            }) {
+                Ok(texture_bind_group) => {
+                    *self = WgpuTexture::Loaded { texture_bind_group };
                 }
+                Err(error) => {
+                    if let Some(callback) = &resources.on_texture_load_error {
+                        callback(error);
+                    };
+                    *self = WgpuTexture::Failed {
+                        path: std::mem::take(path),
+                        attempts: *attempts + 1,
+                        last_try: resources.texture_loading.failed(*attempts + 1),
+                        mipmaps: *mipmaps,
+                        sampler_desc: sampler_desc.clone(),
+                    };
+                }
+            };
+        };
+
+        match self {
+            WgpuTexture::Loaded { texture_bind_group } => Some(texture_bind_group),
+            _ => None,
+        }
+    }
+}
+
+/// Loads and uploads the textures of every page of `atlas`, and creates the
+/// pipelines needed to draw them, so the first frame drawing them doesn't
+/// have to wait.
+///
+/// # Panics
+///
+/// If the spine context wasn't initialized with [`init_wgpu_spine_context`].
+pub(crate) fn preload(
+    render_state: &RenderState,
+    atlas: &Atlas,
+    premultiplied_alpha: bool,
+    cull_mode: Option<Face>,
+) {
+    // NOTE: Holding the renderer lock prevents any callback from accessing
+    // the renderer objects of the pages while they are being loaded.
+    let mut renderer = render_state.renderer.write();
+    let resources: &mut WgpuResources = renderer
+        .callback_resources
+        .get_mut()
+        .expect("the wgpu spine context should be initialized");
+
+    for page in atlas.pages() {
+        // SAFETY: `WgpuTexture` is the registered type in
+        // `set_create_texture_cb`.
+        let spine_texture = unsafe { (*page.c_ptr()).rendererObject.cast::<WgpuTexture>() };
+        // SAFETY: The renderer object is only accessed by the callbacks, and
+        // lives as long as the atlas.
+        if let Some(spine_texture) = unsafe { spine_texture.as_mut() } {
+            spine_texture.poll(resources, premultiplied_alpha, true);
+        }
+    }
+
+    let cull_mode = cull_mode.map(Face::into_wgpu_face);
+    for blend_mode in [
+        BlendMode::Normal,
+        BlendMode::Additive,
+        BlendMode::Multiply,
+        BlendMode::Screen,
+    ] {
+        let blend_state = SpineBlendMode::from(blend_mode).into_blend_state(premultiplied_alpha);
+        resources.pipeline(resources.surface_target, blend_state, cull_mode);
+    }
+}
+
+/// Draws the meshes of `callback` into an offscreen texture of `width` by `height` pixels,
+/// cleared to transparent, and returns its RGBA8 pixels, with straight alpha.
+///
+/// NOTE: Textures still loading are waited for instead of skipping their
+/// meshes.
+///
+/// # Panics
+///
+/// If the spine context wasn't initialized with [`init_wgpu_spine_context`].
+#[cfg(feature = "image")]
+pub(crate) fn render_offscreen(
+    render_state: &RenderState,
+    callback: &RendererCallback,
+    width: u32,
+    height: u32,
+) -> Vec<u8> {
+    let mut renderer = render_state.renderer.write();
+    let resources: &mut WgpuResources = renderer
+        .callback_resources
+        .get_mut()
+        .expect("the wgpu spine context should be initialized");
+    let device = resources.device.clone();
+    let queue = resources.queue.clone();
+
+    // NOTE: The colors of the textures are converted for the surface format
+    // when they are uploaded, so the offscreen texture has to match whether
+    // it is sRGB.
+    let format = match resources.surface_target.format.is_srgb() {
+        true => TextureFormat::Rgba8UnormSrgb,
+        false => TextureFormat::Rgba8Unorm,
+    };
+
+    // NOTE: Frames push and upload into the same buffers only while their
+    // callbacks are prepared, which can't happen while the renderer is locked.
+    let target = RenderTarget {
+        format,
+        samples: 1,
+        depth_format: None,
+    };
+    let prepared = resources.push_callback(&device, &queue, callback, target, true);
+    resources.meshes.upload(&device, &queue);
+    resources.scene.views.clear();
+
+    let size = Extent3d {
+        width,
+        height,
+        depth_or_array_layers: 1,
+    };
+    let texture = device.create_texture(&TextureDescriptor {
+        label: Some("Spine Offscreen Texture"),
+        size,
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: TextureDimension::D2,
+        format,
+        usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&TextureViewDescriptor::default());
+
+    let row_len = width * 4;
+    let padded_row_len = row_len.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT);
+    let buffer = device.create_buffer(&BufferDescriptor {
+        label: Some("Spine Offscreen Buffer"),
+        size: BufferAddress::from(padded_row_len) * BufferAddress::from(height),
+        usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor {
+        label: Some("Spine Offscreen Encoder"),
+    });
+    {
+        let mut render_pass = encoder.begin_render_pass(&RenderPassDescriptor {
+            label: Some("Spine Offscreen Render Pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: &view,
+                resolve_target: None,
+                ops: Operations {
+                    load: LoadOp::Clear(Color::TRANSPARENT),
+                    store: StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        resources.draw_callback(&mut render_pass, &prepared);
+    }
+    encoder.copy_texture_to_buffer(
+        TexelCopyTextureInfo {
+            texture: &texture,
+            mip_level: 0,
+            origin: Origin3d::ZERO,
+            aspect: TextureAspect::All,
+        },
+        TexelCopyBufferInfo {
+            buffer: &buffer,
+            layout: TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row_len),
+                rows_per_image: None,
+            },
+        },
+        size,
+    );
+    queue.submit([encoder.finish()]);
+
+    let slice = buffer.slice(..);
+    let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+    slice.map_async(MapMode::Read, move |result| {
+        let _ = sender.send(result);
+    });
+    let _ = device.poll(Maintain::Wait);
+    receiver
+        .recv()
+        .ok()
+        .and_then(Result::ok)
+        .expect("the offscreen buffer should be mapped");
+
+    let mut pixels = strip_row_padding(
+        &slice.get_mapped_range(),
+        row_len as usize,
+        padded_row_len as usize,
+    );
+    // NOTE: Everything is blended into the transparent texture with
+    // premultiplied alpha.
+    for pixel in pixels.chunks_exact_mut(4) {
+        let alpha = u16::from(pixel[3]);
+        for channel in &mut pixel[..3] {
+            if let Some(value) = (u16::from(*channel) * 255 + alpha / 2).checked_div(alpha) {
+                *channel = value.min(255) as u8;
             }
         }
+    }
+    pixels
+}
+
+/// Copies the rows of `row_len` bytes out of `mapped`, in which every row is
+/// padded to `padded_row_len` bytes for [`COPY_BYTES_PER_ROW_ALIGNMENT`].
+#[cfg(feature = "image")]
+fn strip_row_padding(mapped: &[u8], row_len: usize, padded_row_len: usize) -> Vec<u8> {
+    mapped
+        .chunks(padded_row_len)
+        .flat_map(|row| &row[..row_len])
+        .copied()
+        .collect()
+}
+
+/// A texture page loaded on a worker thread.
+enum TextureData {
+    /// The width, height and RGBA8 pixels returned by the texture decoder.
+    Rgba(u32, u32, Vec<u8>),
+    /// A KTX2 container, which is uploaded without decoding it.
+    Ktx2(Vec<u8>),
+}
+
+impl TextureData {
+    fn load(path: &str, texture_loading: &TextureLoading) -> PendingTexture<Self> {
+        let TextureLoading {
+            loader, decoder, ..
+        } = texture_loading.clone();
+        let path = path.to_owned();
+        PendingTexture::spawn(move || {
+            let bytes = loader(&path)?;
+            if Ktx2::is_ktx2(&bytes) {
+                return Ok(Self::Ktx2(bytes));
+            }
+            let (width, height, pixels) = super::checked_decode(&*decoder, &bytes)?;
+            Ok(Self::Rgba(width, height, pixels))
+        })
+    }
+}
+
+fn set_spine_callbacks(
+    fallback_filter: FilterMode,
+    filter_override: Option<FilterMode>,
+    anisotropy: u16,
+    texture_loading: TextureLoading,
+) {
+    rusty_spine::extension::set_create_texture_cb(move |page, path| {
+        // NOTE: Returns the filter mode, and the mipmap filter mode if the
+        // filter samples from mipmaps.
+        let convert_filter = |filter| match filter {
+            AtlasFilter::Nearest => (FilterMode::Nearest, None),
+            AtlasFilter::Linear => (FilterMode::Linear, None),
+            AtlasFilter::MipmapNearestNearest => (FilterMode::Nearest, Some(FilterMode::Nearest)),
+            AtlasFilter::MipmapLinearNearest => (FilterMode::Linear, Some(FilterMode::Nearest)),
+            AtlasFilter::MipmapNearestLinear => (FilterMode::Nearest, Some(FilterMode::Linear)),
+            AtlasFilter::Mipmap | AtlasFilter::MipmapLinearLinear => {
+                (FilterMode::Linear, Some(FilterMode::Linear))
+            }
+            filter => {
+                log::warn!("Unsupported texture filter mode {filter:?} in atlas page {path}");
+                (fallback_filter, None)
+            }
+        };
+        let convert_wrap = |wrap| match wrap {
+            AtlasWrap::MirroredRepeat => AddressMode::MirrorRepeat,
+            AtlasWrap::ClampToEdge => AddressMode::ClampToEdge,
+            AtlasWrap::Repeat => AddressMode::Repeat,
+            wrap => {
+                log::warn!("Unsupported texture wrap mode {wrap:?} in atlas page {path}");
+                AddressMode::ClampToEdge
+            }
+        };
+        // NOTE: Magnification never samples from mipmaps.
+        let (mag_filter, _) = convert_filter(page.mag_filter());
+        let (min_filter, mipmap_filter) = convert_filter(page.min_filter());
+        let (mag_filter, min_filter, mipmap_filter) = match filter_override {
+            Some(filter) => (filter, filter, mipmap_filter.map(|_| filter)),
+            None => (mag_filter, min_filter, mipmap_filter),
+        };
+        // NOTE: Anisotropic samplers need every filter to be linear.
+        let anisotropy_clamp = match filter_override.is_none() && mipmap_filter.is_some() {
+            true => anisotropy.clamp(1, 16),
+            false => 1,
+        };
+        let (mag_filter, min_filter, mipmap_filter) = match anisotropy_clamp > 1 {
+            true => (
+                FilterMode::Linear,
+                FilterMode::Linear,
+                Some(FilterMode::Linear),
+            ),
+            false => (mag_filter, min_filter, mipmap_filter),
+        };
         page.renderer_object().set(WgpuTexture::Loading {
             path: path.to_owned().into_boxed_str(),
+            attempts: 0,
+            pending: TextureData::load(path, &texture_loading),
+            mipmaps: mipmap_filter.is_some(),
             sampler_desc: SamplerDescriptor {
                 label: Some("Spine Texture Sampler Descriptor"),
                 address_mode_u: convert_wrap(page.u_wrap()),
                 address_mode_v: convert_wrap(page.v_wrap()),
-                mag_filter: convert_filter(page.mag_filter()),
-                min_filter: convert_filter(page.min_filter()),
+                mag_filter,
+                min_filter,
+                mipmap_filter: mipmap_filter.unwrap_or_default(),
+                anisotropy_clamp,
                 ..Default::default()
             },
         });
@@ -426,6 +1382,65 @@ fn set_spine_callbacks() {
         unsafe { page.renderer_object().dispose::<WgpuTexture>() });
 }
 
-fn nonzero(val: BufferAddress) -> NonZero<BufferAddress> {
-    NonZero::new(val).expect("value is not zero")
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui_wgpu::wgpu::naga::{
+        front::wgsl,
+        proc::Layouter,
+        valid::{Capabilities, ValidationFlags, Validator},
+    };
+
+    #[test]
+    fn scene_uniform_matches_shader() {
+        let module = wgsl::parse_str(WgpuContextOptions::DEFAULT_SHADER).unwrap();
+        Validator::new(ValidationFlags::all(), Capabilities::all())
+            .validate(&module)
+            .unwrap();
+        let mut layouter = Layouter::default();
+        layouter.update(module.to_ctx()).unwrap();
+
+        let (_, scene) = module
+            .global_variables
+            .iter()
+            .find(|(_, variable)| variable.name.as_deref() == Some("scene"))
+            .unwrap();
+        // NOTE: The bytes written for every scene must be exactly the
+        // `min_binding_size` of the layout, which must fit the shader's view.
+        let uniform = SceneUniform::new(Mat4::IDENTITY, [0.0; 4], 0.0, 0.0);
+        assert_eq!(
+            bytemuck::bytes_of(&uniform).len(),
+            size_of::<SceneUniform>()
+        );
+        assert_eq!(layouter[scene.ty].size as usize, size_of::<SceneUniform>());
+    }
+
+    #[test]
+    fn compressed_format_keeps_srgb_of_vk_format() {
+        // NOTE: Every supported `VkFormat` is followed by its sRGB version.
+        for vk_format in [131, 133, 135, 137, 145, 147, 149, 151, 157] {
+            let unorm = compressed_format(vk_format).unwrap();
+            let srgb = compressed_format(vk_format + 1).unwrap();
+            assert!(!unorm.is_srgb(), "{vk_format}");
+            assert_eq!(unorm.add_srgb_suffix(), srgb, "{vk_format}");
+        }
+        assert_eq!(compressed_format(139), None);
+    }
+
+    #[test]
+    #[cfg(feature = "image")]
+    fn strip_row_padding_keeps_width_times_height_pixels() {
+        let (width, height) = (3_usize, 2);
+        let row_len = width * 4;
+        let padded_row_len = row_len.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT as usize);
+        let mut mapped = vec![u8::MAX; padded_row_len * height];
+        for (y, row) in mapped.chunks_mut(padded_row_len).enumerate() {
+            row[..row_len].fill(y as u8);
+        }
+
+        let pixels = strip_row_padding(&mapped, row_len, padded_row_len);
+        assert_eq!(pixels.len(), width * height * 4);
+        assert!(pixels[..row_len].iter().all(|&byte| byte == 0));
+        assert!(pixels[row_len..].iter().all(|&byte| byte == 1));
+    }
 }